@@ -1,8 +1,5 @@
-use nom::{
-    character::complete::{i32 as i32_parser, line_ending, space1},
-    multi::separated_list1,
-    IResult,
-};
+use nom::{character::complete::space1, IResult};
+use utils::parsers::integer_lines;
 
 enum LevelChange {
     Increasing,
@@ -10,14 +7,9 @@ enum LevelChange {
     Unknown,
 }
 
-fn parse_levels(input: &str) -> IResult<&str, Vec<i32>> {
-    // space delimited, any number, at least 1
-    separated_list1(space1, i32_parser)(input)
-}
-
 fn parser(s: &str) -> IResult<&str, Vec<Vec<i32>>> {
     // Parse reports (lines), into levels (space delimited numbers)
-    separated_list1(line_ending, parse_levels)(s)
+    integer_lines(space1)(s)
 }
 
 fn is_report_safe(report: &[i32]) -> (bool, Vec<bool>) {
@@ -0,0 +1,173 @@
+//! A tiny interpreter for `name(arg,arg,...)` instructions hidden in corrupted memory
+//!
+//! Instructions are registered by name together with the number of arguments they take, and run
+//! against a state of the caller's choosing. Anything that isn't a registered instruction with
+//! the right number of arguments is skipped while parsing.
+#![allow(dead_code)]
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::char,
+    error::{Error, ErrorKind},
+    multi::separated_list0,
+    sequence::{delimited, preceded},
+    IResult,
+};
+use utils::parsers::{integer, PuzzleInt};
+
+/// How many arguments an instruction takes
+pub enum Arity {
+    Exactly(usize),
+    Any,
+}
+
+impl Arity {
+    fn accepts(&self, num_args: usize) -> bool {
+        match self {
+            Arity::Exactly(n) => *n == num_args,
+            Arity::Any => true,
+        }
+    }
+}
+
+pub trait Instruction<S, A> {
+    fn execute(&self, args: &[A], state: &mut S);
+}
+
+impl<S, A, F> Instruction<S, A> for F
+where
+    F: Fn(&[A], &mut S),
+{
+    fn execute(&self, args: &[A], state: &mut S) {
+        self(args, state)
+    }
+}
+
+struct Opcode<S, A> {
+    name: String,
+    arity: Arity,
+    handler: Box<dyn Instruction<S, A>>,
+}
+
+/// A parsed instruction, ready to be run by the interpreter that parsed it
+pub struct Call<A> {
+    opcode: usize,
+    pub args: Vec<A>,
+}
+
+pub struct Interpreter<S, A> {
+    opcodes: Vec<Opcode<S, A>>,
+}
+
+impl<S, A> Interpreter<S, A>
+where
+    A: PuzzleInt,
+{
+    pub fn new() -> Self {
+        Interpreter {
+            opcodes: Vec::new(),
+        }
+    }
+
+    pub fn register(
+        &mut self,
+        name: &str,
+        arity: Arity,
+        handler: impl Instruction<S, A> + 'static,
+    ) -> &mut Self {
+        self.opcodes.push(Opcode {
+            name: name.to_owned(),
+            arity,
+            handler: Box::new(handler),
+        });
+        self
+    }
+
+    fn parse_call<'a>(&self, input: &'a str) -> IResult<&'a str, Call<A>> {
+        for (opcode, op) in self.opcodes.iter().enumerate() {
+            let parsed: IResult<&str, Vec<A>> = preceded(
+                tag(op.name.as_str()),
+                delimited(char('('), separated_list0(char(','), integer), char(')')),
+            )(input);
+            // Overflowing arguments are just more corruption
+            if let Ok((rest, args)) = parsed {
+                if op.arity.accepts(args.len()) {
+                    return Ok((rest, Call { opcode, args }));
+                }
+            }
+        }
+        Err(nom::Err::Error(Error::new(input, ErrorKind::Alt)))
+    }
+
+    /// Finds every registered instruction in the input, in order
+    pub fn parser<'a>(&self, mut input: &'a str) -> IResult<&'a str, Vec<Call<A>>> {
+        let mut calls = Vec::new();
+        while let Some(c) = input.chars().next() {
+            match self.parse_call(input) {
+                Ok((rest, call)) => {
+                    calls.push(call);
+                    input = rest;
+                }
+                Err(_) => input = &input[c.len_utf8()..],
+            }
+        }
+        Ok((input, calls))
+    }
+
+    pub fn run(&self, calls: &[Call<A>], state: &mut S) {
+        for call in calls {
+            self.opcodes[call.opcode].handler.execute(&call.args, state);
+        }
+    }
+}
+
+impl<S, A> Default for Interpreter<S, A>
+where
+    A: PuzzleInt,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Arity, Interpreter};
+
+    #[test]
+    fn test_interpreter() {
+        // A stack machine with a variadic push and a two argument instruction
+        let mut interpreter = Interpreter::<Vec<i64>, i64>::new();
+        interpreter
+            .register("push", Arity::Any, |args: &[i64], stack: &mut Vec<i64>| {
+                stack.extend_from_slice(args)
+            })
+            .register(
+                "add",
+                Arity::Exactly(0),
+                |_: &[i64], stack: &mut Vec<i64>| {
+                    let sum = stack.pop().unwrap() + stack.pop().unwrap();
+                    stack.push(sum);
+                },
+            )
+            .register(
+                "scale",
+                Arity::Exactly(2),
+                |args: &[i64], stack: &mut Vec<i64>| {
+                    for v in stack.iter_mut() {
+                        *v = *v * args[0] / args[1];
+                    }
+                },
+            );
+
+        let (_, calls) = interpreter
+            .parser("xpush(1,-2,3)add(1)!add()scale(10)scale(4,2)push()")
+            .unwrap();
+        assert_eq!(calls.len(), 4);
+        assert_eq!(calls[0].args, vec![1, -2, 3]);
+
+        let mut stack = Vec::new();
+        interpreter.run(&calls, &mut stack);
+        assert_eq!(stack, vec![2, 2]);
+    }
+}
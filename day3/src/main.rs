@@ -1,17 +1,21 @@
+mod interpreter;
+
+use interpreter::{Arity, Call, Interpreter};
 use nom::{
-    branch::alt,
-    bytes::complete::{tag, take},
-    character::complete::u32 as u32_parser,
-    combinator::map,
-    multi::{many1, many_till},
+    bytes::complete::tag,
+    multi::many1,
     sequence::{delimited, separated_pair},
     IResult,
 };
-use utils::drop_until;
+use utils::{drop_until, parsers::integer};
 
 // Part 1
 fn parse_mul_params(input: &str) -> IResult<&str, (u32, u32)> {
-    separated_pair(u32_parser, tag(","), u32_parser)(input)
+    // Overflowing arguments are just more corruption, not a reason to stop looking
+    separated_pair(integer, tag(","), integer)(input).map_err(|e| match e {
+        nom::Err::Failure(e) => nom::Err::Error(e),
+        e => e,
+    })
 }
 
 fn parse_mul(input: &str) -> IResult<&str, (u32, u32)> {
@@ -23,60 +27,44 @@ fn parser(s: &str) -> IResult<&str, Vec<(u32, u32)>> {
     many1(drop_until(parse_mul))(s)
 }
 
-fn parse_mul_params2(input: &str) -> IResult<&str, ParsedValue> {
-    nom::combinator::map(separated_pair(u32_parser, tag(","), u32_parser), |v| {
-        ParsedValue::Multiply(v)
-    })(input)
-}
-
 // Part 2
-enum ParsedValue {
-    Multiply((u32, u32)),
-    Do,
-    Dont,
-}
-
-fn parse_mul2(input: &str) -> IResult<&str, ParsedValue> {
-    delimited(tag("mul("), parse_mul_params2, tag(")"))(input)
-}
-
-fn parse_do(input: &str) -> IResult<&str, ParsedValue> {
-    nom::combinator::map(tag("do()"), |_| ParsedValue::Do)(input)
-}
-
-fn parse_dont(input: &str) -> IResult<&str, ParsedValue> {
-    nom::combinator::map(tag("don't()"), |_| ParsedValue::Dont)(input)
+struct Machine {
+    multiply_enabled: bool,
+    accumulator: u64,
 }
 
-// Returns Vec<(first number, second number)>
-fn parser2(s: &str) -> IResult<&str, Vec<ParsedValue>> {
-    many1(map(
-        many_till(take(1u8), alt((parse_mul2, parse_do, parse_dont))),
-        |(_, matched)| matched,
-    ))(s)
+fn interpreter() -> Interpreter<Machine, u32> {
+    let mut interpreter = Interpreter::new();
+    interpreter
+        .register("mul", Arity::Exactly(2), |args: &[u32], m: &mut Machine| {
+            if m.multiply_enabled {
+                m.accumulator += u64::from(args[0]) * u64::from(args[1]);
+            }
+        })
+        .register("do", Arity::Exactly(0), |_: &[u32], m: &mut Machine| {
+            m.multiply_enabled = true
+        })
+        .register("don't", Arity::Exactly(0), |_: &[u32], m: &mut Machine| {
+            m.multiply_enabled = false
+        });
+    interpreter
 }
 
-fn solve(multiply_pairs: &[(u32, u32)]) -> u32 {
-    // multiply and add!
-    multiply_pairs.iter().fold(0, |a, p| a + (p.0 * p.1))
+fn solve(multiply_pairs: &[(u32, u32)]) -> u64 {
+    // multiply and add! (widened, so the products can't overflow)
+    multiply_pairs
+        .iter()
+        .fold(0, |a, p| a + (u64::from(p.0) * u64::from(p.1)))
 }
 
-fn solve2(parsed_values: &[ParsedValue]) -> u32 {
+fn solve2(interpreter: &Interpreter<Machine, u32>, calls: &[Call<u32>]) -> u64 {
     // Start with multiply enabled, then turn off on don'ts and back on with dos
-    let mut do_multiply: bool = true;
-    let mut accumulator = 0;
-    for value in parsed_values {
-        match value {
-            ParsedValue::Multiply((first, second)) => {
-                if do_multiply {
-                    accumulator += first * second;
-                }
-            }
-            ParsedValue::Do => do_multiply = true,
-            ParsedValue::Dont => do_multiply = false,
-        }
-    }
-    accumulator
+    let mut machine = Machine {
+        multiply_enabled: true,
+        accumulator: 0,
+    };
+    interpreter.run(calls, &mut machine);
+    machine.accumulator
 }
 
 fn main() {
@@ -87,8 +75,9 @@ fn main() {
         product_sums
     );
 
-    let parsed_values = utils::load_puzzle_data(3, parser2);
-    let product_sums = solve2(&parsed_values);
+    let interpreter = interpreter();
+    let calls = utils::load_puzzle_data(3, |s| interpreter.parser(s));
+    let product_sums = solve2(&interpreter, &calls);
     println!(
         "Solution 2: The sum of the multiplied values, following dos and don'ts is {}",
         product_sums
@@ -97,7 +86,15 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use crate::{parser, parser2, solve, solve2};
+    use crate::{interpreter, parser, solve, solve2};
+
+    #[test]
+    fn test_parser_overflow() {
+        assert_eq!(
+            parser("xmul(2,4)mul(99999999999,2)mul(3,5)"),
+            Ok(("", vec![(2, 4), (3, 5)]))
+        );
+    }
 
     #[test]
     fn test_puzzle() {
@@ -108,8 +105,9 @@ mod tests {
 
     #[test]
     fn test_puzzle2() {
-        let test_data = utils::load_puzzle_test(3, 2, parser2);
-        let solution = solve2(&test_data);
+        let interpreter = interpreter();
+        let test_data = utils::load_puzzle_test(3, 2, |s| interpreter.parser(s));
+        let solution = solve2(&interpreter, &test_data);
         assert_eq!(solution, 48);
     }
 }
@@ -1,78 +1,26 @@
 use nom::{
     character::complete::{alpha1, line_ending},
+    combinator::map_res,
     multi::separated_list1,
     IResult,
 };
-use utils::{Grid, StaticGrid};
+use utils::{pattern::Pattern, word_search::WordSearch, StaticGrid};
 
 fn parser(s: &str) -> IResult<&str, StaticGrid<char>> {
-    // Odd way of going about it, I know, but it ensures it parses
-    let rows = separated_list1(line_ending, alpha1)(s)?;
-    let grid: StaticGrid<char> = StaticGrid {
-        cells: rows.1.concat().chars().collect(),
-        num_rows: rows.1.len(),
-        num_cols: rows.1[0].len(),
-    };
-    Ok(("", grid))
+    map_res(separated_list1(line_ending, alpha1), |rows: Vec<&str>| {
+        StaticGrid::from_rows(rows.iter().map(|r| r.chars().collect()).collect())
+    })(s)
 }
 
 fn solve(grid: &StaticGrid<char>) -> u32 {
-    // Find all of the word "XMAS" in the grid
-    let mut count = 0;
-    for y in 0..grid.num_rows as isize {
-        for x in 0..grid.num_cols as isize {
-            // Only start at X's
-            if grid.get_cell(x, y) != Some(&'X') {
-                continue;
-            }
-            // Check every direction for XMAS
-            for direction in enum_iterator::all::<utils::CardinalDirection>() {
-                if grid
-                    .direction_iter_at(x, y, direction)
-                    .take(4)
-                    .collect::<String>()
-                    .as_str()
-                    == "XMAS"
-                {
-                    count += 1;
-                }
-            }
-        }
-    }
-    count
+    // Find all of the word "XMAS" in the grid, in every direction
+    WordSearch::new(["XMAS"]).find(grid).len() as u32
 }
 
 fn solve2(grid: &StaticGrid<char>) -> u32 {
-    // Find all "MAS" in an X pattern
-    let mut count = 0;
-    for y in 0..grid.num_rows as isize {
-        for x in 0..grid.num_cols as isize {
-            // Only start at A's (the center of the X)
-            if grid.get_cell(x, y) != Some(&'A') {
-                continue;
-            }
-            // Check NE and SW, one must be a 'S' and the other a 'M'
-            // Do the same for NW and SE
-            let ne = grid
-                .direction_iter_at(x, y, utils::CardinalDirection::NorthEast)
-                .skip(1).take(1).collect::<Vec<&char>>();
-            let sw = grid
-                .direction_iter_at(x, y, utils::CardinalDirection::SouthWest)
-                .skip(1).take(1).collect::<Vec<&char>>();
-            let nw = grid
-                .direction_iter_at(x, y, utils::CardinalDirection::NorthWest)
-                .skip(1).take(1).collect::<Vec<&char>>();
-            let se = grid
-                .direction_iter_at(x, y, utils::CardinalDirection::SouthEast)
-                .skip(1).take(1).collect::<Vec<&char>>();
-            if ((ne.first() == Some(&&'M') && sw.first() == Some(&&'S')) || (ne.first() == Some(&&'S') && sw.first() == Some(&&'M')))
-                && ((nw.first() == Some(&&'M') && se.first() == Some(&&'S')) || (nw.first() == Some(&&'S') && se.first() == Some(&&'M')))
-            {
-                count += 1;
-            }
-        }
-    }
-    count
+    // Find all "MAS" in an X pattern, in any rotation or reflection
+    let x_mas: Pattern<char> = "M.S/.A./M.S".parse().expect("valid pattern");
+    x_mas.with_symmetries().find(grid).count() as u32
 }
 
 fn main() {
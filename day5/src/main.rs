@@ -2,16 +2,20 @@
 
 use nom::{
     bytes::complete::tag,
-    character::complete::{line_ending, u32 as u32_parser},
+    character::complete::line_ending,
     multi::{many1, separated_list1},
     sequence::separated_pair,
     IResult,
 };
+use utils::{
+    graph::RuleOrder,
+    parsers::{integer, integer_lines},
+};
 
 type PuzzleInput = (Vec<(u32, u32)>, Vec<Vec<u32>>);
 
 fn num_pair(input: &str) -> IResult<&str, (u32, u32)> {
-    separated_pair(u32_parser, tag("|"), u32_parser)(input)
+    separated_pair(integer, tag("|"), integer)(input)
 }
 
 fn parser(s: &str) -> IResult<&str, PuzzleInput> {
@@ -25,87 +29,38 @@ fn parser(s: &str) -> IResult<&str, PuzzleInput> {
     separated_pair(
         separated_list1(line_ending, num_pair),
         many1(line_ending),
-        separated_list1(line_ending, separated_list1(tag(","), u32_parser)),
+        integer_lines(tag(",")),
     )(s)
 }
 
 fn solve(input: &PuzzleInput) -> usize {
-    // Search through each update
-    let ordered_updates: Vec<&Vec<u32>> = input
+    let rules: RuleOrder<u32> = input.0.iter().copied().collect();
+
+    // Find the middles of the ordered updates, add them up
+    input
         .1
         .iter()
-        .filter(|&update| {
-            update.iter().enumerate().all(|(page_ndx, page)| {
-                // Is this page in a rule?
-                input
-                    .0
-                    .iter()
-                    .filter(|&rule| rule.0 == *page)
-                    .all(|matched_rule| {
-                        match update.iter().position(|&m| m == matched_rule.1) {
-                            Some(found_page_ndx) => found_page_ndx > page_ndx,
-                            // It's in order if the number doesn't exist
-                            None => true,
-                        }
-                    })
-            })
+        .filter(|update| rules.is_ordered(update))
+        .fold(0, |a, update| {
+            a + *update.get(update.len().div_floor(2)).unwrap() as usize
         })
-        .collect();
-
-    // Find the middles, add them up
-    ordered_updates.iter().fold(0, |a, &update| {
-        a + *update.get(update.len().div_floor(2)).unwrap() as usize
-    })
 }
 
 fn solve2(input: &mut PuzzleInput) -> usize {
-    // Search through each update, finding invalid ones
-    let mut un_ordered_updates: Vec<&mut Vec<u32>> = input
-        .1
-        .iter_mut()
-        .filter(|update| {
-            !update.iter().enumerate().all(|(page_ndx, page)| {
-                // Is this page in a rule?
-                input
-                    .0
-                    .iter()
-                    .filter(|&rule| rule.0 == *page)
-                    .all(|matched_rule| {
-                        match update.iter().position(|&m| m == matched_rule.1) {
-                            Some(found_page_ndx) => found_page_ndx > page_ndx,
-                            // It's in order if the number doesn't exist
-                            None => true,
-                        }
-                    })
-            })
-        })
-        .collect();
+    let rules: RuleOrder<u32> = input.0.iter().copied().collect();
 
-    // Now order them...
-    for update in un_ordered_updates.iter_mut() {
-        update.sort_by(|a, b| {
-            // Is there a sort rule for these numbers?
-            match input
-                .0
-                .iter()
-                .find(|&rule| (rule.0 == *a && rule.1 == *b) || (rule.1 == *a && rule.0 == *b))
-            {
-                Some(a_rule) => {
-                    if a_rule.0 == *a {
-                        std::cmp::Ordering::Greater
-                    } else {
-                        std::cmp::Ordering::Less
-                    }
-                }
-                None => std::cmp::Ordering::Equal,
-            }
-        });
+    // Order the invalid updates using only the rules for their own pages
+    let mut middle_sum = 0;
+    for update in input.1.iter_mut() {
+        if rules.is_ordered(update) {
+            continue;
+        }
+        *update = rules
+            .sort(update)
+            .unwrap_or_else(|e| panic!("Unable to order update {update:?}: {e}"));
+        middle_sum += *update.get(update.len().div_floor(2)).unwrap() as usize;
     }
-
-    // Find the middles, add them up
-    un_ordered_updates.iter().fold(0, |a, update| {
-        a + *update.get(update.len().div_floor(2)).unwrap() as usize
-    })
+    middle_sum
 }
 
 fn main() {
@@ -7,7 +7,7 @@ use nom::{
     multi::{many1, separated_list1},
     IResult,
 };
-use utils::{CardinalDirection, Point, StaticGrid};
+use utils::{CardinalDirection, Grid, Point, StaticGrid};
 
 #[derive(Default, Clone, Debug)]
 struct Cell {
@@ -51,7 +51,7 @@ impl fmt::Display for Game {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut rows = String::new();
 
-        for row_ndx in 0..self.grid.num_rows {
+        for row_ndx in 0..self.grid.num_rows() {
             let row_str: String = self
                 .grid
                 .row(row_ndx)
@@ -107,26 +107,17 @@ fn parse_cell_row(s: &str) -> IResult<&str, Vec<CellVariant>> {
 fn parser(s: &str) -> IResult<&str, Game> {
     // Odd way of going about it, I know, but it ensures it parses
     let rows = separated_list1(line_ending, parse_cell_row)(s)?;
-    let mut player_position = Point::new(0, 0);
-    let mut player_direction = CardinalDirection::North;
-    let grid = StaticGrid {
-        cells: rows
-            .1
+    let grid = StaticGrid::from_rows(
+        rows.1
             .iter()
-            .enumerate()
-            .flat_map(|(row_ndx, cvs)| {
+            .map(|cvs| {
                 cvs.iter()
-                    .enumerate()
-                    .map(|(col_ndx, cv)| match cv {
-                        CellVariant::Player(cardinal_direction) => {
-                            player_position = Point::new(col_ndx as isize, row_ndx as isize);
-                            player_direction = *cardinal_direction;
-                            Cell {
-                                visited: true,
-                                visited_dir: vec![*cardinal_direction],
-                                ..Default::default()
-                            }
-                        }
+                    .map(|cv| match cv {
+                        CellVariant::Player(cardinal_direction) => Cell {
+                            visited: true,
+                            visited_dir: vec![*cardinal_direction],
+                            ..Default::default()
+                        },
                         CellVariant::Obstruction => Cell {
                             obstruction: true,
                             ..Default::default()
@@ -135,12 +126,18 @@ fn parser(s: &str) -> IResult<&str, Game> {
                             ..Default::default()
                         },
                     })
-                    .collect::<Vec<Cell>>()
+                    .collect()
             })
             .collect(),
-        num_rows: rows.1.len(),
-        num_cols: rows.1[0].len(),
-    };
+    )
+    .map_err(|_| nom::Err::Failure(nom::error::Error::new(s, nom::error::ErrorKind::Verify)))?;
+
+    // The player is on the only visited cell
+    let player_position = grid.position(|c| c.visited).unwrap_or_default();
+    let player_direction = grid
+        .get(player_position)
+        .and_then(|c| c.visited_dir.first().copied())
+        .unwrap_or(CardinalDirection::North);
 
     let game = Game {
         starting_position: player_position,
@@ -175,7 +172,7 @@ fn solve(game: &mut Game) -> usize {
             }
             c.visited = true;
             c.visited_dir.push(game.player_direction);
-            game.player_position.add(1, game.player_direction);
+            game.player_position.step_in_place(game.player_direction, 1);
 
             if it.peek().is_none() {
                 playing = false;
@@ -260,7 +257,7 @@ fn solve2(game: &mut Game) -> usize {
             }
 
             // Move the player...
-            game.player_position.add(1, game.player_direction);
+            game.player_position.step_in_place(game.player_direction, 1);
 
             // If we're at the edge of the board...
             if it.peek().is_none() {
@@ -0,0 +1,475 @@
+//! Directed graphs, e.g. day5's page ordering rules
+//!
+//! Nodes can be any hashable value. They are numbered in the order they are first seen and the
+//! edges are kept in one flat list, sorted by source then target, so lookups don't chase pointers.
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{self, Debug, Display},
+    hash::Hash,
+};
+
+#[derive(Debug, Clone)]
+pub struct DiGraph<N> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    /// The targets of node `i` are `targets[offsets[i]..offsets[i + 1]]`, sorted
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+}
+
+/// A topological sort failed, because these nodes form a cycle (each has an edge to the next, and
+/// the last to the first)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<N> {
+    pub cycle: Vec<N>,
+}
+
+/// Writes "cycle: a b c a"
+fn write_cycle<N: Debug>(f: &mut fmt::Formatter, cycle: &[N]) -> fmt::Result {
+    write!(f, "cycle:")?;
+    for node in cycle.iter().chain(cycle.first()) {
+        write!(f, " {node:?}")?;
+    }
+    Ok(())
+}
+
+impl<N: Debug> Display for CycleError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_cycle(f, &self.cycle)
+    }
+}
+
+impl<N: Debug> std::error::Error for CycleError<N> {}
+
+impl<N> DiGraph<N>
+where
+    N: Clone + Eq + Hash,
+{
+    /// Builds a graph from `(from, to)` edges, repeated edges are only kept once
+    pub fn from_edges<I: IntoIterator<Item = (N, N)>>(edges: I) -> Self {
+        let mut nodes = Vec::new();
+        let mut index = HashMap::new();
+        let mut node_ndx = |node: N| {
+            *index.entry(node.clone()).or_insert_with(|| {
+                nodes.push(node);
+                nodes.len() - 1
+            })
+        };
+        let mut edges: Vec<(usize, usize)> = edges
+            .into_iter()
+            .map(|(from, to)| (node_ndx(from), node_ndx(to)))
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+
+        let mut offsets = vec![0; nodes.len() + 1];
+        for &(from, _) in &edges {
+            offsets[from + 1] += 1;
+        }
+        for ndx in 1..offsets.len() {
+            offsets[ndx] += offsets[ndx - 1];
+        }
+        DiGraph {
+            nodes,
+            index,
+            offsets,
+            targets: edges.into_iter().map(|(_, to)| to).collect(),
+        }
+    }
+
+    /// Every node, in the order they were first seen
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.index.contains_key(node)
+    }
+
+    fn targets_of(&self, ndx: usize) -> &[usize] {
+        &self.targets[self.offsets[ndx]..self.offsets[ndx + 1]]
+    }
+
+    pub fn has_edge(&self, from: &N, to: &N) -> bool {
+        match (self.index.get(from), self.index.get(to)) {
+            (Some(&from), Some(to)) => self.targets_of(from).binary_search(to).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// The nodes `node` has an edge to, in the order they were first seen
+    pub fn successors(&self, node: &N) -> impl Iterator<Item = &N> {
+        let targets = self
+            .index
+            .get(node)
+            .map_or(&[][..], |&ndx| self.targets_of(ndx));
+        targets.iter().map(|&ndx| &self.nodes[ndx])
+    }
+
+    fn reachable_ndx(&self, from: usize) -> Vec<bool> {
+        let mut seen = vec![false; self.nodes.len()];
+        seen[from] = true;
+        let mut to_visit = vec![from];
+        while let Some(ndx) = to_visit.pop() {
+            for &next in self.targets_of(ndx) {
+                if !seen[next] {
+                    seen[next] = true;
+                    to_visit.push(next);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Every node that can be reached by following edges from `from`, including itself
+    pub fn reachable_from(&self, from: &N) -> Vec<N> {
+        let Some(&from) = self.index.get(from) else {
+            return Vec::new();
+        };
+        self.reachable_ndx(from)
+            .into_iter()
+            .zip(&self.nodes)
+            .filter(|(seen, _)| *seen)
+            .map(|(_, node)| node.clone())
+            .collect()
+    }
+
+    /// True if there is a path from `from` to `to`. A node always reaches itself.
+    pub fn is_reachable(&self, from: &N, to: &N) -> bool {
+        match (self.index.get(from), self.index.get(to)) {
+            (Some(&from), Some(&to)) => self.reachable_ndx(from)[to],
+            _ => from == to,
+        }
+    }
+
+    /// Finds a cycle among the nodes `allowed` lets through, with a depth first search
+    fn find_cycle_ndx(&self, allowed: impl Fn(usize) -> bool) -> Option<Vec<usize>> {
+        // Position on the current path, if the node is on it
+        let mut on_path: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut done = vec![false; self.nodes.len()];
+        for root in (0..self.nodes.len()).filter(|&ndx| allowed(ndx)) {
+            if done[root] {
+                continue;
+            }
+            // Each path entry is a node and how many of its edges have been followed
+            let mut path = vec![(root, 0)];
+            on_path[root] = Some(0);
+            while let Some((ndx, next_edge)) = path.last_mut() {
+                let ndx = *ndx;
+                let Some(&next) = self.targets_of(ndx).get(*next_edge) else {
+                    path.pop();
+                    on_path[ndx] = None;
+                    done[ndx] = true;
+                    continue;
+                };
+                *next_edge += 1;
+                if !allowed(next) || done[next] {
+                    continue;
+                }
+                if let Some(start) = on_path[next] {
+                    return Some(path[start..].iter().map(|&(ndx, _)| ndx).collect());
+                }
+                on_path[next] = Some(path.len());
+                path.push((next, 0));
+            }
+        }
+        None
+    }
+
+    /// Any cycle in the graph, each node having an edge to the next and the last to the first
+    pub fn find_cycle(&self) -> Option<Vec<N>> {
+        self.find_cycle_ndx(|_| true)
+            .map(|cycle| self.to_nodes(&cycle))
+    }
+
+    fn to_nodes(&self, ndxs: &[usize]) -> Vec<N> {
+        ndxs.iter().map(|&ndx| self.nodes[ndx].clone()).collect()
+    }
+
+    /// Orders every node so that all edges point forwards
+    pub fn topological_sort(&self) -> Result<Vec<N>, CycleError<N>> {
+        self.topological_sort_subset(&self.nodes)
+    }
+
+    /// Orders just the `subset` nodes so that the edges between them point forwards
+    ///
+    /// Edges through nodes outside the subset are ignored. Subset nodes that aren't in the graph
+    /// have no edges and come first, and nodes that become free to place at the same time keep
+    /// their subset order. Repeated nodes are only given once.
+    pub fn topological_sort_subset(&self, subset: &[N]) -> Result<Vec<N>, CycleError<N>> {
+        let mut in_subset = vec![false; self.nodes.len()];
+        let mut outside_graph = Vec::new();
+        let mut members = Vec::with_capacity(subset.len());
+        for node in subset {
+            match self.index.get(node) {
+                Some(&ndx) if !in_subset[ndx] => {
+                    in_subset[ndx] = true;
+                    members.push(Some(ndx));
+                }
+                None if !outside_graph.contains(node) => {
+                    outside_graph.push(node.clone());
+                    members.push(None);
+                }
+                _ => (),
+            }
+        }
+
+        let mut in_degree = vec![0; self.nodes.len()];
+        for &ndx in members.iter().flatten() {
+            for &next in self.targets_of(ndx) {
+                if in_subset[next] {
+                    in_degree[next] += 1;
+                }
+            }
+        }
+
+        let mut sorted = Vec::with_capacity(members.len());
+        let mut outside_graph = outside_graph.into_iter();
+        let mut ready = VecDeque::new();
+        for member in &members {
+            match member {
+                Some(ndx) if in_degree[*ndx] == 0 => ready.push_back(*ndx),
+                Some(_) => (),
+                None => sorted.extend(outside_graph.next()),
+            }
+        }
+        let first_in_graph = sorted.len();
+        while let Some(ndx) = ready.pop_front() {
+            sorted.push(self.nodes[ndx].clone());
+            for &next in self.targets_of(ndx) {
+                if in_subset[next] {
+                    in_degree[next] -= 1;
+                    if in_degree[next] == 0 {
+                        ready.push_back(next);
+                    }
+                }
+            }
+        }
+
+        if sorted.len() - first_in_graph < members.iter().flatten().count() {
+            // Whatever couldn't be placed is on or behind a cycle
+            let cycle = self
+                .find_cycle_ndx(|ndx| in_subset[ndx] && in_degree[ndx] > 0)
+                .expect("unsorted nodes contain a cycle");
+            return Err(CycleError {
+                cycle: self.to_nodes(&cycle),
+            });
+        }
+        Ok(sorted)
+    }
+}
+
+impl<N> FromIterator<(N, N)> for DiGraph<N>
+where
+    N: Clone + Eq + Hash,
+{
+    fn from_iter<I: IntoIterator<Item = (N, N)>>(iter: I) -> Self {
+        DiGraph::from_edges(iter)
+    }
+}
+
+/// Why `RuleOrder::sort` couldn't give the one correct order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderError<T> {
+    /// The rules between these items form a cycle, so no order obeys them all
+    Contradiction { cycle: Vec<T> },
+    /// No rule decides which of these neighbours goes first, so more than one order is valid
+    Ambiguous { first: T, second: T },
+    /// The item was given more than once, so it can't have a single place in the order
+    Repeated { item: T },
+}
+
+impl<T: Debug> Display for OrderError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OrderError::Contradiction { cycle } => {
+                write!(f, "contradictory rules, ")?;
+                write_cycle(f, cycle)
+            }
+            OrderError::Ambiguous { first, second } => {
+                write!(f, "no rule orders {first:?} and {second:?}")
+            }
+            OrderError::Repeated { item } => write!(f, "{item:?} is given more than once"),
+        }
+    }
+}
+
+impl<T: Debug> std::error::Error for OrderError<T> {}
+
+/// An ordering given by `(before, after)` rules, like day5's page ordering rules
+///
+/// Only items with a rule between them are ordered, so unlike a `sort_by` comparator it never has
+/// to pretend unrelated items are equal.
+#[derive(Debug, Clone)]
+pub struct RuleOrder<T> {
+    rules: DiGraph<T>,
+}
+
+impl<T> RuleOrder<T>
+where
+    T: Clone + Eq + Hash,
+{
+    pub fn new<I: IntoIterator<Item = (T, T)>>(rules: I) -> Self {
+        RuleOrder {
+            rules: DiGraph::from_edges(rules),
+        }
+    }
+
+    /// True if `before` has to come before `after`, by a rule of its own
+    pub fn has_rule(&self, before: &T, after: &T) -> bool {
+        self.rules.has_edge(before, after)
+    }
+
+    /// The rules the sequence breaks, as `(before, after)` pairs
+    pub fn violations(&self, items: &[T]) -> Vec<(T, T)> {
+        let mut broken = Vec::new();
+        for (ndx, item) in items.iter().enumerate() {
+            for later in &items[ndx + 1..] {
+                if self.has_rule(later, item) {
+                    broken.push((later.clone(), item.clone()));
+                }
+            }
+        }
+        broken
+    }
+
+    /// True if no rule puts a later item before an earlier one
+    pub fn is_ordered(&self, items: &[T]) -> bool {
+        items.iter().enumerate().all(|(ndx, item)| {
+            items[ndx + 1..]
+                .iter()
+                .all(|later| !self.has_rule(later, item))
+        })
+    }
+
+    /// The only order of the items that obeys the rules between them
+    ///
+    /// Fails if an item is repeated, or the rules contradict each other, or don't decide between
+    /// two items. Use `DiGraph::topological_sort_subset` when any valid order will do.
+    pub fn sort(&self, items: &[T]) -> Result<Vec<T>, OrderError<T>> {
+        let mut seen = HashSet::with_capacity(items.len());
+        if let Some(item) = items.iter().find(|&item| !seen.insert(item)) {
+            return Err(OrderError::Repeated { item: item.clone() });
+        }
+        let sorted = self
+            .rules
+            .topological_sort_subset(items)
+            .map_err(|CycleError { cycle }| OrderError::Contradiction { cycle })?;
+        // The order is only unique if every item is forced before the next
+        if let Some(pair) = sorted
+            .windows(2)
+            .find(|pair| !self.has_rule(&pair[0], &pair[1]))
+        {
+            return Err(OrderError::Ambiguous {
+                first: pair[0].clone(),
+                second: pair[1].clone(),
+            });
+        }
+        Ok(sorted)
+    }
+}
+
+impl<T> FromIterator<(T, T)> for RuleOrder<T>
+where
+    T: Clone + Eq + Hash,
+{
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        RuleOrder::new(iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CycleError, DiGraph, OrderError, RuleOrder};
+
+    #[test]
+    fn test_graph() {
+        let rules = [(47, 53), (97, 13), (97, 61), (97, 47), (75, 29), (61, 13)];
+        let graph: DiGraph<u32> = rules.into_iter().chain([(97, 13)]).collect();
+        assert_eq!(graph.node_count(), 7);
+        assert_eq!(graph.edge_count(), 6);
+        assert!(graph.has_edge(&97, &61));
+        assert!(!graph.has_edge(&61, &97));
+        assert_eq!(
+            graph.successors(&97).collect::<Vec<_>>(),
+            vec![&47, &13, &61]
+        );
+        assert!(graph.is_reachable(&97, &53));
+        assert!(!graph.is_reachable(&53, &97));
+        assert!(graph.is_reachable(&1, &1));
+        assert_eq!(graph.reachable_from(&47), vec![47, 53]);
+        assert_eq!(graph.find_cycle(), None);
+
+        let sorted = graph.topological_sort().unwrap();
+        assert!(rules.iter().all(|(a, b)| {
+            sorted.iter().position(|n| n == a) < sorted.iter().position(|n| n == b)
+        }));
+        // Only the rules between these pages count, 1 isn't in any rule
+        assert_eq!(
+            graph.topological_sort_subset(&[61, 13, 1, 97]),
+            Ok(vec![1, 97, 61, 13])
+        );
+
+        let graph: DiGraph<u32> = rules.into_iter().chain([(13, 47), (53, 97)]).collect();
+        assert_eq!(graph.find_cycle(), Some(vec![47, 53, 97]));
+        // The cycles don't matter for pages that aren't on them
+        assert_eq!(graph.topological_sort_subset(&[75, 29]), Ok(vec![75, 29]));
+        assert_eq!(
+            graph.topological_sort_subset(&[13, 97, 61]),
+            Ok(vec![97, 61, 13])
+        );
+        assert_eq!(
+            graph.topological_sort_subset(&[47, 53, 61, 75, 97]),
+            Err(CycleError {
+                cycle: vec![47, 53, 97]
+            })
+        );
+        assert_eq!(CycleError { cycle: vec![1, 2] }.to_string(), "cycle: 1 2 1");
+    }
+
+    #[test]
+    fn test_rule_order() {
+        let rules: RuleOrder<u32> = [(97, 13), (97, 47), (47, 13), (75, 29), (61, 13)]
+            .into_iter()
+            .collect();
+        assert!(rules.is_ordered(&[97, 47, 13]));
+        assert!(rules.is_ordered(&[75, 61, 1]));
+        assert!(!rules.is_ordered(&[47, 13, 97]));
+        assert_eq!(rules.violations(&[47, 13, 97]), vec![(97, 47), (97, 13)]);
+
+        assert_eq!(rules.sort(&[13, 47, 97]), Ok(vec![97, 47, 13]));
+        assert_eq!(
+            rules.sort(&[13, 47, 13, 97]),
+            Err(OrderError::Repeated { item: 13 })
+        );
+        // Both 97 and 61 must come before 13, but nothing orders them
+        assert_eq!(
+            rules.sort(&[13, 97, 61]),
+            Err(OrderError::Ambiguous {
+                first: 97,
+                second: 61
+            })
+        );
+
+        let rules: RuleOrder<u32> = [(1, 2), (2, 3), (3, 1), (3, 4)].into_iter().collect();
+        assert_eq!(rules.sort(&[3, 4]), Ok(vec![3, 4]));
+        assert_eq!(
+            rules.sort(&[4, 3, 2, 1]),
+            Err(OrderError::Contradiction {
+                cycle: vec![1, 2, 3]
+            })
+        );
+        assert_eq!(
+            rules.sort(&[4, 3, 2, 1]).unwrap_err().to_string(),
+            "contradictory rules, cycle: 1 2 3 1"
+        );
+    }
+}
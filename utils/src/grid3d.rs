@@ -0,0 +1,392 @@
+//! 3D counterparts of `Point` and the 2D grids
+use std::{
+    collections::HashMap,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+};
+
+use crate::GridError;
+
+#[derive(Clone, Default, Debug, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3D {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+impl Point3D {
+    pub fn new(x: isize, y: isize, z: isize) -> Point3D {
+        Point3D { x, y, z }
+    }
+
+    /// Distance moving only along the axes
+    pub fn manhattan(&self, other: Point3D) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// The 6 points sharing a face with this one
+    pub fn neighbors6(&self) -> impl Iterator<Item = Point3D> {
+        let center = *self;
+        [
+            Point3D::new(-1, 0, 0),
+            Point3D::new(1, 0, 0),
+            Point3D::new(0, -1, 0),
+            Point3D::new(0, 1, 0),
+            Point3D::new(0, 0, -1),
+            Point3D::new(0, 0, 1),
+        ]
+        .into_iter()
+        .map(move |offset| center + offset)
+    }
+
+    /// The 26 points sharing a face, edge or corner with this one
+    pub fn neighbors26(&self) -> impl Iterator<Item = Point3D> {
+        let center = *self;
+        (-1..=1)
+            .flat_map(|z| (-1..=1).flat_map(move |y| (-1..=1).map(move |x| Point3D::new(x, y, z))))
+            .filter(|offset| *offset != Point3D::default())
+            .map(move |offset| center + offset)
+    }
+}
+
+impl Add for Point3D {
+    type Output = Point3D;
+
+    fn add(self, rhs: Point3D) -> Self::Output {
+        Point3D::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for Point3D {
+    fn add_assign(&mut self, rhs: Point3D) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point3D {
+    type Output = Point3D;
+
+    fn sub(self, rhs: Point3D) -> Self::Output {
+        Point3D::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl SubAssign for Point3D {
+    fn sub_assign(&mut self, rhs: Point3D) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Point3D {
+    type Output = Point3D;
+
+    fn neg(self) -> Self::Output {
+        Point3D::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<isize> for Point3D {
+    type Output = Point3D;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Point3D::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+/*
+    Traits
+*/
+pub trait Grid3D {
+    type Item;
+
+    fn get_cell(&self, point: Point3D) -> Option<&Self::Item>;
+    fn get_cell_mut(&mut self, point: Point3D) -> Option<&mut Self::Item>;
+
+    fn first_cell_coord(&self) -> Point3D;
+    fn last_cell_coord(&self) -> Point3D;
+
+    /// Face neighbours that are in the grid, with their coordinates
+    fn neighbors6(&self, point: Point3D) -> impl Iterator<Item = (Point3D, &Self::Item)> {
+        point
+            .neighbors6()
+            .filter_map(|p| self.get_cell(p).map(|cell| (p, cell)))
+    }
+
+    /// Face, edge and corner neighbours that are in the grid, with their coordinates
+    fn neighbors26(&self, point: Point3D) -> impl Iterator<Item = (Point3D, &Self::Item)> {
+        point
+            .neighbors26()
+            .filter_map(|p| self.get_cell(p).map(|cell| (p, cell)))
+    }
+}
+
+/// A fixed size 3D grid, stored x fastest, then y, then z
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StaticGrid3D<T> {
+    cells: Vec<T>,
+    size_x: usize,
+    size_y: usize,
+    size_z: usize,
+}
+
+impl<T> StaticGrid3D<T> {
+    /// The number of cells, if every cell can be addressed with a Point3D
+    fn check_size(size_x: usize, size_y: usize, size_z: usize) -> Result<usize, GridError> {
+        match size_x
+            .checked_mul(size_y)
+            .and_then(|n| n.checked_mul(size_z))
+        {
+            Some(len)
+                if [size_x, size_y, size_z]
+                    .iter()
+                    .all(|&size| size <= isize::MAX as usize) =>
+            {
+                Ok(len)
+            }
+            _ => Err(GridError::TooLarge3D {
+                size_x,
+                size_y,
+                size_z,
+            }),
+        }
+    }
+
+    /// Builds a grid by calling `f` with the coordinate of each cell
+    pub fn from_fn<F: FnMut(Point3D) -> T>(
+        size_x: usize,
+        size_y: usize,
+        size_z: usize,
+        mut f: F,
+    ) -> Result<Self, GridError> {
+        let len = Self::check_size(size_x, size_y, size_z)?;
+        let mut cells = Vec::with_capacity(len);
+        for z in 0..size_z as isize {
+            for y in 0..size_y as isize {
+                for x in 0..size_x as isize {
+                    cells.push(f(Point3D::new(x, y, z)));
+                }
+            }
+        }
+        Ok(StaticGrid3D {
+            cells,
+            size_x,
+            size_y,
+            size_z,
+        })
+    }
+
+    /// (x, y, z) sizes
+    pub fn size(&self) -> (usize, usize, usize) {
+        (self.size_x, self.size_y, self.size_z)
+    }
+
+    /// All cells, x fastest, then y, then z
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    fn index_of(&self, point: Point3D) -> Option<usize> {
+        let in_bounds = |v: isize, size: usize| v >= 0 && (v as usize) < size;
+        if in_bounds(point.x, self.size_x)
+            && in_bounds(point.y, self.size_y)
+            && in_bounds(point.z, self.size_z)
+        {
+            Some(
+                (point.z as usize * self.size_y + point.y as usize) * self.size_x
+                    + point.x as usize,
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> StaticGrid3D<T>
+where
+    T: Default + Clone,
+{
+    /// A grid filled with the default value
+    ///
+    /// Panics if the grid is too large, use `from_fn` to get a `GridError` instead.
+    pub fn new(size_x: usize, size_y: usize, size_z: usize) -> Self {
+        let len = Self::check_size(size_x, size_y, size_z).unwrap_or_else(|e| panic!("{e}"));
+        StaticGrid3D {
+            cells: vec![Default::default(); len],
+            size_x,
+            size_y,
+            size_z,
+        }
+    }
+}
+
+impl<T> Grid3D for StaticGrid3D<T> {
+    type Item = T;
+
+    fn get_cell(&self, point: Point3D) -> Option<&Self::Item> {
+        self.index_of(point).map(|ndx| &self.cells[ndx])
+    }
+
+    fn get_cell_mut(&mut self, point: Point3D) -> Option<&mut Self::Item> {
+        self.index_of(point).map(|ndx| &mut self.cells[ndx])
+    }
+
+    fn first_cell_coord(&self) -> Point3D {
+        Point3D::new(0, 0, 0)
+    }
+
+    fn last_cell_coord(&self) -> Point3D {
+        Point3D::new(
+            self.size_x as isize - 1,
+            self.size_y as isize - 1,
+            self.size_z as isize - 1,
+        )
+    }
+}
+
+/// A 3D grid that only stores the cells that have been set, for huge or mostly empty spaces
+#[derive(Debug, Default, Clone)]
+pub struct SparseGrid3D<T> {
+    cells: HashMap<Point3D, T>,
+}
+
+impl<T> SparseGrid3D<T> {
+    pub fn new() -> Self {
+        SparseGrid3D {
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Sets a cell, returning the previous value
+    pub fn insert(&mut self, point: Point3D, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: Point3D) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Iterates over the set cells in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point3D, &T)> {
+        self.cells.iter().map(|(p, cell)| (*p, cell))
+    }
+
+    pub fn get_cell_or_add_mut(&mut self, point: Point3D) -> &mut T
+    where
+        T: Default,
+    {
+        self.cells.entry(point).or_default()
+    }
+}
+
+impl<T> Grid3D for SparseGrid3D<T> {
+    type Item = T;
+
+    fn get_cell(&self, point: Point3D) -> Option<&Self::Item> {
+        self.cells.get(&point)
+    }
+
+    fn get_cell_mut(&mut self, point: Point3D) -> Option<&mut Self::Item> {
+        self.cells.get_mut(&point)
+    }
+
+    /// Lowest corner of the bounding box of the set cells, (0, 0, 0) when empty
+    fn first_cell_coord(&self) -> Point3D {
+        self.cells
+            .keys()
+            .copied()
+            .reduce(|a, b| Point3D::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)))
+            .unwrap_or_default()
+    }
+
+    /// Highest corner of the bounding box of the set cells, (0, 0, 0) when empty
+    fn last_cell_coord(&self) -> Point3D {
+        self.cells
+            .keys()
+            .copied()
+            .reduce(|a, b| Point3D::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)))
+            .unwrap_or_default()
+    }
+}
+
+impl<T> FromIterator<(Point3D, T)> for SparseGrid3D<T> {
+    fn from_iter<I: IntoIterator<Item = (Point3D, T)>>(iter: I) -> Self {
+        SparseGrid3D {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Panics if the point is outside the grid, use `get_cell` to check
+impl<T> Index<Point3D> for StaticGrid3D<T> {
+    type Output = T;
+
+    fn index(&self, point: Point3D) -> &Self::Output {
+        self.get_cell(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Point3D> for StaticGrid3D<T> {
+    fn index_mut(&mut self, point: Point3D) -> &mut Self::Output {
+        self.get_cell_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid3D, Point3D, SparseGrid3D, StaticGrid3D};
+    use crate::GridError;
+
+    #[test]
+    fn test_point3d() {
+        let p = Point3D::new(1, 2, 3);
+        assert_eq!(p + Point3D::new(1, 1, 1) * 2, Point3D::new(3, 4, 5));
+        assert_eq!(p.manhattan(-p), 12);
+        assert_eq!(p.neighbors6().count(), 6);
+        assert!(p.neighbors6().all(|n| n.manhattan(p) == 1));
+        assert_eq!(p.neighbors26().count(), 26);
+        assert!(!p.neighbors26().any(|n| n == p));
+    }
+
+    #[test]
+    fn test_grids_3d() {
+        let mut g = StaticGrid3D::from_fn(2, 3, 4, |p| p.x + p.y * 10 + p.z * 100).unwrap();
+        assert_eq!(g.size(), (2, 3, 4));
+        assert_eq!(g[Point3D::new(1, 2, 3)], 321);
+        g[Point3D::new(0, 0, 0)] = -1;
+        assert_eq!(g.get_cell(Point3D::new(2, 0, 0)), None);
+        assert_eq!(g.last_cell_coord(), Point3D::new(1, 2, 3));
+        // A corner only has 3 face neighbours inside the grid
+        assert_eq!(g.neighbors6(Point3D::new(0, 0, 0)).count(), 3);
+        assert_eq!(g.neighbors26(Point3D::new(0, 0, 0)).count(), 7);
+        assert_eq!(
+            StaticGrid3D::from_fn(usize::MAX, 2, 1, |_| ()).map(|g| g.size()),
+            Err(GridError::TooLarge3D {
+                size_x: usize::MAX,
+                size_y: 2,
+                size_z: 1
+            })
+        );
+
+        // Surface area of a droplet: faces not touching another cube
+        let droplet: SparseGrid3D<bool> = [(1, 1, 1), (2, 1, 1)]
+            .into_iter()
+            .map(|(x, y, z)| (Point3D::new(x, y, z), true))
+            .collect();
+        let surface: usize = droplet
+            .iter()
+            .map(|(p, _)| 6 - droplet.neighbors6(p).count())
+            .sum();
+        assert_eq!(surface, 10);
+        assert_eq!(droplet.first_cell_coord(), Point3D::new(1, 1, 1));
+        assert_eq!(droplet.last_cell_coord(), Point3D::new(2, 1, 1));
+    }
+}
@@ -1,15 +1,41 @@
 #![allow(dead_code)]
 
-use nom::{bytes::complete::take, combinator::map, multi::many_till, IResult};
+// Lets the AocParse derive refer to `::utils` from inside this crate too
+extern crate self as utils;
+
+use nom::{
+    bytes::complete::take,
+    character::complete::{alphanumeric1, anychar, line_ending},
+    combinator::map,
+    multi::{many_till, separated_list1},
+    IResult,
+};
 use std::{
     cmp::Ordering,
+    collections::{HashMap, HashSet, VecDeque},
     fmt::{self, Display},
     fs,
+    io::{BufRead, BufReader},
+    iter::{FusedIterator, StepBy},
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
     path::Path,
+    slice,
+    str::FromStr,
 };
 
 use enum_iterator::Sequence;
 
+pub mod graph;
+pub mod grid3d;
+pub mod parsers;
+pub mod pattern;
+pub mod search;
+pub mod word_search;
+
+pub use nom;
+#[cfg(feature = "derive")]
+pub use utils_derive::AocParse;
+
 // Now with nom parser
 fn load_puzzle<T, F: FnOnce(&str) -> IResult<&str, T>>(puzzle_path: &Path, parser: F) -> T {
     parser(&String::from_utf8(fs::read(puzzle_path).expect("Unable to open input!")).unwrap())
@@ -33,6 +59,128 @@ pub fn load_puzzle_test<T, F: FnOnce(&str) -> IResult<&str, T>>(
     load_puzzle(puzzle_path, parser)
 }
 
+/// Iterator that reads an input one line at a time and parses each line into a record
+///
+/// Blank lines are skipped. Panics if reading or parsing a line fails, like `load_puzzle_data`, or
+/// if the parser leaves anything but trailing whitespace on the line.
+pub struct PuzzleLines<R, F> {
+    reader: R,
+    parser: F,
+    line: String,
+    line_number: usize,
+}
+
+impl<R, F> PuzzleLines<R, F> {
+    pub fn new(reader: R, parser: F) -> Self {
+        PuzzleLines {
+            reader,
+            parser,
+            line: String::new(),
+            line_number: 0,
+        }
+    }
+}
+
+impl<R, T, F> Iterator for PuzzleLines<R, F>
+where
+    R: BufRead,
+    F: FnMut(&str) -> IResult<&str, T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            if self
+                .reader
+                .read_line(&mut self.line)
+                .expect("Unable to read input!")
+                == 0
+            {
+                return None;
+            }
+            self.line_number += 1;
+            let line = self.line.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                continue;
+            }
+            match (self.parser)(line) {
+                Ok((rest, record)) if rest.trim_end().is_empty() => return Some(record),
+                Ok((rest, _)) => panic!(
+                    "Unable to parse input line {}! Unparsed: {rest:?}",
+                    self.line_number
+                ),
+                Err(e) => panic!("Unable to parse input line {}! {e}", self.line_number),
+            }
+        }
+    }
+}
+
+fn stream_puzzle<T, F: FnMut(&str) -> IResult<&str, T>>(
+    puzzle_path: &Path,
+    parser: F,
+) -> PuzzleLines<BufReader<fs::File>, F> {
+    PuzzleLines::new(
+        BufReader::new(fs::File::open(puzzle_path).expect("Unable to open input!")),
+        parser,
+    )
+}
+
+/// Like `load_puzzle_data`, but parses one line at a time instead of reading the whole file
+pub fn stream_puzzle_data<T, F: FnMut(&str) -> IResult<&str, T>>(
+    day: u32,
+    parser: F,
+) -> PuzzleLines<BufReader<fs::File>, F> {
+    let puzzle_filename = format!("puzzles/day{day}.txt");
+    stream_puzzle(Path::new(&puzzle_filename), parser)
+}
+
+pub fn stream_puzzle_test<T, F: FnMut(&str) -> IResult<&str, T>>(
+    day: u32,
+    test_number: u32,
+    parser: F,
+) -> PuzzleLines<BufReader<fs::File>, F> {
+    let puzzle_filename = format!("../puzzles/day{day}_test{test_number}.txt");
+    stream_puzzle(Path::new(&puzzle_filename), parser)
+}
+
+/// A type that knows how to parse itself from puzzle input
+///
+/// Usually derived (`derive` feature), see `utils_derive` for the supported attributes.
+pub trait AocParse: Sized {
+    fn parse(input: &str) -> IResult<&str, Self>;
+}
+
+macro_rules! impl_aoc_parse_int {
+    ($($t:ty),*) => {
+        $(impl AocParse for $t {
+            fn parse(input: &str) -> IResult<&str, Self> {
+                parsers::integer(input)
+            }
+        })*
+    };
+}
+
+impl_aoc_parse_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl AocParse for char {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        anychar(input)
+    }
+}
+
+/// Parses a word (one or more alphanumeric characters)
+impl AocParse for String {
+    fn parse(input: &str) -> IResult<&str, Self> {
+        map(alphanumeric1, str::to_owned)(input)
+    }
+}
+
+/// Parses one T per line
+pub fn parse_lines<T: AocParse>(input: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(line_ending, T::parse)(input)
+}
+
 // Thanks Trequetrum! (https://github.com/rust-bakery/nom/issues/1594)
 pub fn drop_until<'a, T>(
     parser: fn(&'a str) -> IResult<&'a str, T>,
@@ -82,6 +230,345 @@ pub trait Grid {
     fn last_cell_coord(&self) -> Point;
 
     fn get_row(&self, y: isize) -> Option<&[Self::Item]>;
+
+    fn get(&self, point: Point) -> Option<&Self::Item> {
+        self.get_cell(point.x, point.y)
+    }
+
+    fn get_mut(&mut self, point: Point) -> Option<&mut Self::Item> {
+        self.get_cell_mut(point.x, point.y)
+    }
+
+    fn width(&self) -> usize {
+        (self.last_cell_coord().x - self.first_cell_coord().x + 1) as usize
+    }
+
+    fn height(&self) -> usize {
+        (self.last_cell_coord().y - self.first_cell_coord().y + 1) as usize
+    }
+
+    fn contains(&self, point: Point) -> bool {
+        let (first, last) = (self.first_cell_coord(), self.last_cell_coord());
+        (first.x..=last.x).contains(&point.x) && (first.y..=last.y).contains(&point.y)
+    }
+
+    /// Every coordinate in the grid, row by row
+    fn coords(&self) -> GridCoordinateIter {
+        GridCoordinateIter::new(self.first_cell_coord(), self.last_cell_coord())
+    }
+
+    /// Every cell in the grid with its coordinate, row by row
+    fn iter_with_coords(&self) -> impl Iterator<Item = (Point, &Self::Item)>
+    where
+        Self: Sized,
+    {
+        self.coords()
+            .filter_map(|p| self.get(p).map(|cell| (p, cell)))
+    }
+
+    /// Coordinate of the first cell (row by row) matching the predicate
+    fn position<P>(&self, mut predicate: P) -> Option<Point>
+    where
+        Self: Sized,
+        P: FnMut(&Self::Item) -> bool,
+    {
+        self.iter_with_coords()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(p, _)| p)
+    }
+
+    /// Coordinates of every cell matching the predicate, row by row
+    fn positions<P>(&self, mut predicate: P) -> impl Iterator<Item = Point>
+    where
+        Self: Sized,
+        P: FnMut(&Self::Item) -> bool,
+    {
+        self.iter_with_coords()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(p, _)| p)
+    }
+
+    /// The up to 4 orthogonal neighbours in the grid, clockwise from North
+    fn neighbors4(&self, point: Point) -> impl Iterator<Item = (Point, &Self::Item)>
+    where
+        Self: Sized,
+    {
+        self.neighbors8(point)
+            .filter(move |(p, _)| p.x == point.x || p.y == point.y)
+    }
+
+    /// The up to 8 surrounding neighbours in the grid, clockwise from North
+    fn neighbors8(&self, point: Point) -> impl Iterator<Item = (Point, &Self::Item)>
+    where
+        Self: Sized,
+    {
+        enum_iterator::all::<CardinalDirection>().filter_map(move |direction| {
+            let p = point.step(direction, 1);
+            self.get(p).map(|cell| (p, cell))
+        })
+    }
+
+    /// Calls `f` on each of the up to 4 orthogonal neighbours, clockwise from North
+    fn for_each_neighbor4_mut(&mut self, point: Point, mut f: impl FnMut(Point, &mut Self::Item))
+    where
+        Self: Sized,
+    {
+        for direction in CardinalDirection::ORTHOGONAL {
+            let p = point.step(direction, 1);
+            if let Some(cell) = self.get_mut(p) {
+                f(p, cell);
+            }
+        }
+    }
+
+    /// Calls `f` on each of the up to 8 surrounding neighbours, clockwise from North
+    fn for_each_neighbor8_mut(&mut self, point: Point, mut f: impl FnMut(Point, &mut Self::Item))
+    where
+        Self: Sized,
+    {
+        for direction in CardinalDirection::ALL {
+            let p = point.step(direction, 1);
+            if let Some(cell) = self.get_mut(p) {
+                f(p, cell);
+            }
+        }
+    }
+
+    /// Number of cells matching the predicate
+    fn count<P>(&self, mut predicate: P) -> usize
+    where
+        Self: Sized,
+        P: FnMut(&Self::Item) -> bool,
+    {
+        self.iter_with_coords()
+            .filter(|(_, cell)| predicate(cell))
+            .count()
+    }
+
+    /// A num_rows x num_cols window with its top left cell at `origin`, None if it doesn't fit
+    fn view(&self, origin: Point, num_rows: usize, num_cols: usize) -> Option<GridView<'_, Self>>
+    where
+        Self: Sized,
+    {
+        GridView::new(self, origin, num_rows, num_cols)
+    }
+
+    fn view_mut(
+        &mut self,
+        origin: Point,
+        num_rows: usize,
+        num_cols: usize,
+    ) -> Option<GridViewMut<'_, Self>>
+    where
+        Self: Sized,
+    {
+        GridViewMut::new(self, origin, num_rows, num_cols)
+    }
+
+    /// The neighbours of a point that a move can reach, clockwise from North
+    fn connected(
+        &self,
+        point: Point,
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = (Point, &Self::Item)>
+    where
+        Self: Sized,
+    {
+        self.neighbors8(point)
+            .filter(move |(p, _)| match connectivity {
+                Connectivity::Four => p.x == point.x || p.y == point.y,
+                Connectivity::Eight => true,
+            })
+    }
+
+    /// Every cell in the same region as `start`, in the order they were reached
+    ///
+    /// `same_region(from, to)` decides if a neighbouring cell joins the region. Empty if `start`
+    /// isn't in the grid.
+    fn flood_fill<P>(
+        &self,
+        start: Point,
+        connectivity: Connectivity,
+        mut same_region: P,
+    ) -> Vec<Point>
+    where
+        Self: Sized,
+        P: FnMut(&Self::Item, &Self::Item) -> bool,
+    {
+        let mut region = Vec::new();
+        if self.get(start).is_none() {
+            return region;
+        }
+        let mut seen = HashSet::from([start]);
+        region.push(start);
+        let mut next = 0;
+        while let Some(&point) = region.get(next) {
+            next += 1;
+            let from = self.get(point).expect("only cells in the grid are added");
+            for (p, to) in self.connected(point, connectivity) {
+                if !seen.contains(&p) && same_region(from, to) {
+                    seen.insert(p);
+                    region.push(p);
+                }
+            }
+        }
+        region
+    }
+
+    /// Breadth first search from `start`, only moving onto cells that are `passable`
+    ///
+    /// The start itself is always reached if it is in the grid. Every move costs 1, see
+    /// `GridPaths` for distances, paths and path counts.
+    fn bfs<P>(&self, start: Point, connectivity: Connectivity, mut passable: P) -> GridPaths
+    where
+        Self: Sized,
+        P: FnMut(&Self::Item) -> bool,
+    {
+        let mut paths = GridPaths {
+            start,
+            visits: HashMap::new(),
+        };
+        if self.get(start).is_none() {
+            return paths;
+        }
+        paths.visits.insert(
+            start,
+            Visit {
+                distance: 0,
+                num_paths: 1,
+                previous: None,
+            },
+        );
+        let mut queue = VecDeque::from([start]);
+        while let Some(point) = queue.pop_front() {
+            // Every way to reach this point has been counted by the time it leaves the queue
+            let Visit {
+                distance,
+                num_paths,
+                ..
+            } = paths.visits[&point];
+            for (p, cell) in self.connected(point, connectivity) {
+                match paths.visits.get_mut(&p) {
+                    Some(visit) if visit.distance == distance + 1 => {
+                        visit.num_paths = visit.num_paths.saturating_add(num_paths);
+                    }
+                    Some(_) => (),
+                    None if passable(cell) => {
+                        paths.visits.insert(
+                            p,
+                            Visit {
+                                distance: distance + 1,
+                                num_paths,
+                                previous: Some(point),
+                            },
+                        );
+                        queue.push_back(p);
+                    }
+                    None => (),
+                }
+            }
+        }
+        paths
+    }
+
+    /// Splits the whole grid into regions, see `flood_fill`
+    fn label_components<P>(&self, connectivity: Connectivity, mut same_region: P) -> Components
+    where
+        Self: Sized,
+        P: FnMut(&Self::Item, &Self::Item) -> bool,
+    {
+        let mut components = Components::default();
+        for (point, _) in self.iter_with_coords() {
+            if components.labels.contains_key(&point) {
+                continue;
+            }
+            let region = self.flood_fill(point, connectivity, &mut same_region);
+            let label = components.regions.len();
+            components.labels.extend(region.iter().map(|&p| (p, label)));
+            components.regions.push(region);
+        }
+        components
+    }
+}
+
+/// Which neighbours count as connected, for regions and paths
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Connectivity {
+    /// North, East, South and West
+    Four,
+    /// Diagonals too
+    Eight,
+}
+
+/// The regions of a grid, from `Grid::label_components`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Components {
+    /// Index into `regions` of every cell
+    pub labels: HashMap<Point, usize>,
+    /// The cells of each region, numbered in the order they were found row by row
+    pub regions: Vec<Vec<Point>>,
+}
+
+impl Components {
+    pub fn label(&self, point: Point) -> Option<usize> {
+        self.labels.get(&point).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Visit {
+    distance: usize,
+    num_paths: u64,
+    previous: Option<Point>,
+}
+
+/// Shortest paths from one start point, from `Grid::bfs`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridPaths {
+    start: Point,
+    visits: HashMap<Point, Visit>,
+}
+
+impl GridPaths {
+    pub fn start(&self) -> Point {
+        self.start
+    }
+
+    /// Number of moves to reach the point, None if it can't be reached
+    pub fn distance(&self, point: Point) -> Option<usize> {
+        self.visits.get(&point).map(|visit| visit.distance)
+    }
+
+    /// Every reachable point with its distance, in no particular order
+    pub fn distances(&self) -> impl Iterator<Item = (Point, usize)> + '_ {
+        self.visits.iter().map(|(p, visit)| (*p, visit.distance))
+    }
+
+    /// One shortest path, from the start to the target inclusive
+    pub fn path_to(&self, target: Point) -> Option<Vec<Point>> {
+        let mut path = vec![target];
+        let mut visit = self.visits.get(&target)?;
+        while let Some(previous) = visit.previous {
+            path.push(previous);
+            visit = &self.visits[&previous];
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// How many different shortest paths reach the target, 0 if it can't be reached. Saturates
+    /// at u64::MAX.
+    pub fn count_paths(&self, target: Point) -> u64 {
+        self.visits.get(&target).map_or(0, |visit| visit.num_paths)
+    }
 }
 
 pub trait Growable {
@@ -93,18 +580,302 @@ pub trait Growable {
 
 pub trait GrowableGrid<T>: Growable<Item = T> + Grid<Item = T> {}
 
-#[derive(Debug, Default, Clone)]
+/// A fixed size grid, stored row by row
+///
+/// Invariant: `cells.len() == num_rows * num_cols`, and both dimensions fit in an `isize` so every
+/// cell can be addressed by a `Point`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct StaticGrid<T> {
-    pub cells: Vec<T>,
-    pub num_rows: usize,
-    pub num_cols: usize,
+    cells: Vec<T>,
+    num_rows: usize,
+    num_cols: usize,
+}
+
+impl<T> StaticGrid<T> {
+    fn check_dimensions(num_rows: usize, num_cols: usize) -> Result<(), GridError> {
+        match num_rows.checked_mul(num_cols) {
+            Some(_) if num_rows <= isize::MAX as usize && num_cols <= isize::MAX as usize => Ok(()),
+            _ => Err(GridError::TooLarge { num_rows, num_cols }),
+        }
+    }
+
+    /// Builds a grid from a list of rows, which must all be the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let num_rows = rows.len();
+        let num_cols = rows.first().map_or(0, Vec::len);
+        if let Some((row, bad_row)) = rows.iter().enumerate().find(|(_, r)| r.len() != num_cols) {
+            return Err(GridError::RaggedRow {
+                row,
+                len: bad_row.len(),
+                expected: num_cols,
+            });
+        }
+        Self::check_dimensions(num_rows, num_cols)?;
+        Ok(StaticGrid {
+            cells: rows.into_iter().flatten().collect(),
+            num_rows,
+            num_cols,
+        })
+    }
+
+    /// Builds a grid from cells stored row by row, `num_cols` cells per row
+    pub fn from_vec(cells: Vec<T>, num_cols: usize) -> Result<Self, GridError> {
+        // Zero columns only works for zero cells
+        if !cells.len().is_multiple_of(num_cols) {
+            return Err(GridError::SizeMismatch {
+                len: cells.len(),
+                num_cols,
+            });
+        }
+        let num_rows = cells.len().checked_div(num_cols).unwrap_or(0);
+        Self::check_dimensions(num_rows, num_cols)?;
+        Ok(StaticGrid {
+            cells,
+            num_rows,
+            num_cols,
+        })
+    }
+
+    /// Builds a grid by calling `f` with the coordinate of each cell, row by row
+    pub fn from_fn<F: FnMut(Point) -> T>(
+        num_rows: usize,
+        num_cols: usize,
+        mut f: F,
+    ) -> Result<Self, GridError> {
+        Self::check_dimensions(num_rows, num_cols)?;
+        let mut cells = Vec::with_capacity(num_rows * num_cols);
+        for y in 0..num_rows as isize {
+            for x in 0..num_cols as isize {
+                cells.push(f(Point::new(x, y)));
+            }
+        }
+        Ok(StaticGrid {
+            cells,
+            num_rows,
+            num_cols,
+        })
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    /// All cells, row by row
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    fn check_square(&self) -> Result<(), GridError> {
+        if self.num_rows == self.num_cols {
+            Ok(())
+        } else {
+            Err(GridError::NotSquare {
+                num_rows: self.num_rows,
+                num_cols: self.num_cols,
+            })
+        }
+    }
+
+    /// Mirrors the grid left to right
+    pub fn flip_horizontal_in_place(&mut self) {
+        if self.num_cols > 0 {
+            self.cells
+                .chunks_exact_mut(self.num_cols)
+                .for_each(<[T]>::reverse);
+        }
+    }
+
+    /// Mirrors the grid top to bottom
+    pub fn flip_vertical_in_place(&mut self) {
+        for y in 0..self.num_rows / 2 {
+            let (top, bottom) = self
+                .cells
+                .split_at_mut((self.num_rows - 1 - y) * self.num_cols);
+            top[y * self.num_cols..(y + 1) * self.num_cols]
+                .swap_with_slice(&mut bottom[..self.num_cols]);
+        }
+    }
+
+    pub fn rotate_180_in_place(&mut self) {
+        self.cells.reverse();
+    }
+
+    /// Swaps rows and columns, only possible in place for a square grid
+    pub fn transpose_in_place(&mut self) -> Result<(), GridError> {
+        self.check_square()?;
+        for y in 0..self.num_rows {
+            for x in y + 1..self.num_cols {
+                self.cells
+                    .swap(y * self.num_cols + x, x * self.num_cols + y);
+            }
+        }
+        Ok(())
+    }
+
+    /// Rotates a quarter turn clockwise, only possible in place for a square grid
+    pub fn rotate_cw_in_place(&mut self) -> Result<(), GridError> {
+        self.transpose_in_place()?;
+        self.flip_horizontal_in_place();
+        Ok(())
+    }
+
+    /// Rotates a quarter turn counter clockwise, only possible in place for a square grid
+    pub fn rotate_ccw_in_place(&mut self) -> Result<(), GridError> {
+        self.transpose_in_place()?;
+        self.flip_vertical_in_place();
+        Ok(())
+    }
+
+    /// Every full line of cells in the orientation, each one a `GridLine` that can be reversed
+    ///
+    /// Rows are given top to bottom and columns left to right. Diagonals start at the bottom left
+    /// corner and move up the left edge then along the top edge. Anti-diagonals start at the top
+    /// left corner and move along the top edge then down the right edge.
+    pub fn lines(
+        &self,
+        orientation: LineOrientation,
+    ) -> impl DoubleEndedIterator<Item = GridLine<'_, T>> + ExactSizeIterator {
+        let (rows, cols) = (self.num_rows as isize, self.num_cols as isize);
+        let count = match orientation {
+            _ if self.cells.is_empty() => 0,
+            LineOrientation::Row => self.num_rows,
+            LineOrientation::Col => self.num_cols,
+            LineOrientation::Diagonal | LineOrientation::AntiDiagonal => {
+                self.num_rows + self.num_cols - 1
+            }
+        };
+        (0..count as isize).map(move |n| {
+            let start = match orientation {
+                LineOrientation::Row => Point::new(0, n),
+                LineOrientation::Col => Point::new(n, 0),
+                LineOrientation::Diagonal if n < rows => Point::new(0, rows - 1 - n),
+                LineOrientation::Diagonal => Point::new(n - rows + 1, 0),
+                LineOrientation::AntiDiagonal if n < cols => Point::new(n, 0),
+                LineOrientation::AntiDiagonal => Point::new(cols - 1, n - cols + 1),
+            };
+            self.line_from(start, orientation.direction().offset())
+        })
+    }
+
+    /// The line from `start` to the edge of the grid, stepping by `step`
+    fn line_from(&self, start: Point, step: Point) -> GridLine<'_, T> {
+        let steps_left = |pos: isize, step: isize, size: usize| match step {
+            0 => usize::MAX,
+            1 => size - pos as usize,
+            _ => pos as usize + 1,
+        };
+        GridLine {
+            cells: &self.cells,
+            num_cols: self.num_cols,
+            start,
+            step,
+            len: steps_left(start.x, step.x, self.num_cols).min(steps_left(
+                start.y,
+                step.y,
+                self.num_rows,
+            )),
+        }
+    }
+}
+
+impl<T> StaticGrid<T>
+where
+    T: Clone,
+{
+    /// Builds a num_rows x num_cols grid, copying each cell from `source(new coordinate)`
+    fn remap<F: Fn(isize, isize) -> (isize, isize)>(
+        &self,
+        num_rows: usize,
+        num_cols: usize,
+        source: F,
+    ) -> Self {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for y in 0..num_rows as isize {
+            for x in 0..num_cols as isize {
+                let (src_x, src_y) = source(x, y);
+                cells.push(self.cells[src_y as usize * self.num_cols + src_x as usize].clone());
+            }
+        }
+        StaticGrid {
+            cells,
+            num_rows,
+            num_cols,
+        }
+    }
+
+    /// A copy with rows and columns swapped
+    pub fn transpose(&self) -> Self {
+        self.remap(self.num_cols, self.num_rows, |x, y| (y, x))
+    }
+
+    /// A copy rotated a quarter turn clockwise
+    pub fn rotate_cw(&self) -> Self {
+        let last_row = self.num_rows as isize - 1;
+        self.remap(self.num_cols, self.num_rows, |x, y| (y, last_row - x))
+    }
+
+    /// A copy rotated a quarter turn counter clockwise
+    pub fn rotate_ccw(&self) -> Self {
+        let last_col = self.num_cols as isize - 1;
+        self.remap(self.num_cols, self.num_rows, |x, y| (last_col - y, x))
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        let mut grid = self.clone();
+        grid.rotate_180_in_place();
+        grid
+    }
+
+    /// A copy mirrored left to right
+    pub fn flip_horizontal(&self) -> Self {
+        let mut grid = self.clone();
+        grid.flip_horizontal_in_place();
+        grid
+    }
+
+    /// A copy mirrored top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        let mut grid = self.clone();
+        grid.flip_vertical_in_place();
+        grid
+    }
+
+    /// All 8 rotations and reflections of the grid, starting with an unchanged copy
+    ///
+    /// The first 4 are the clockwise rotations, the last 4 the rotations of the transpose.
+    /// Symmetric grids will give some of the same grids more than once.
+    pub fn symmetries(&self) -> impl Iterator<Item = Self> + '_ {
+        (0..8).map(move |n| {
+            let grid = if n < 4 {
+                self.clone()
+            } else {
+                self.transpose()
+            };
+            match n % 4 {
+                0 => grid,
+                1 => grid.rotate_cw(),
+                2 => grid.rotate_180(),
+                _ => grid.rotate_ccw(),
+            }
+        })
+    }
 }
 
 impl<T> StaticGrid<T>
 where
     T: Default + Clone,
 {
+    /// A grid filled with the default value
+    ///
+    /// Panics if the grid is too large, use `from_fn` to get a `GridError` instead.
     pub fn new(num_rows: usize, num_cols: usize) -> Self {
+        if let Err(e) = Self::check_dimensions(num_rows, num_cols) {
+            panic!("{e}");
+        }
         StaticGrid {
             cells: vec![Default::default(); num_rows * num_cols],
             num_rows,
@@ -120,20 +891,26 @@ where
         &mut self.cells[row_ndx * self.num_cols..(row_ndx * self.num_cols) + self.num_cols]
     }
 
-    pub fn col(&self, col_ndx: usize) -> Vec<&T> {
-        let mut ret_cells: Vec<&T> = Vec::new();
-        for cell in self.cells.iter().skip(col_ndx).step_by(self.num_cols) {
-            ret_cells.push(cell);
-        }
-        ret_cells
+    /// The cells of a column, top to bottom. Panics if the column is outside the grid.
+    pub fn col(&self, col_ndx: usize) -> ColIter<'_, T> {
+        assert!(
+            col_ndx < self.num_cols,
+            "column {col_ndx} is outside the grid"
+        );
+        ColIter(self.cells[col_ndx..].iter().step_by(self.num_cols))
     }
 
-    pub fn col_mut(&mut self, col_ndx: usize) -> Vec<&mut T> {
-        let mut ret_cells: Vec<&mut T> = Vec::new();
-        for cell in self.cells.iter_mut().skip(col_ndx).step_by(self.num_cols) {
-            ret_cells.push(cell);
-        }
-        ret_cells
+    pub fn col_mut(&mut self, col_ndx: usize) -> ColIterMut<'_, T> {
+        assert!(
+            col_ndx < self.num_cols,
+            "column {col_ndx} is outside the grid"
+        );
+        ColIterMut(self.cells[col_ndx..].iter_mut().step_by(self.num_cols))
+    }
+
+    /// Every column, left to right
+    pub fn cols(&self) -> impl DoubleEndedIterator<Item = ColIter<'_, T>> + ExactSizeIterator {
+        (0..self.num_cols).map(|col_ndx| self.col(col_ndx))
     }
 
     /// Returns an iterator over each cell in the grid. Returns the Cell.
@@ -232,10 +1009,502 @@ where
     }
 }
 
+/// Panics if the point is outside the grid, use `get` to check
+impl<T> Index<Point> for StaticGrid<T>
+where
+    T: Default + Clone,
+{
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for StaticGrid<T>
+where
+    T: Default + Clone,
+{
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the grid"))
+    }
+}
+
+/// A StaticGrid whose edges wrap around, so every Point maps to a cell (including negative ones)
+///
+/// Neighbours are reported with wrapped coordinates, and each cell at most once (on grids
+/// narrower than 3 cells East and West can be the same cell). A cell is never its own neighbour. Direction iterators never end.
+/// `contains` still only covers the inner grid's own coordinates, so views can't be larger than
+/// the grid.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WrappingGrid<T> {
+    grid: StaticGrid<T>,
+}
+
+impl<T> WrappingGrid<T> {
+    pub fn new(grid: StaticGrid<T>) -> Self {
+        WrappingGrid { grid }
+    }
+
+    pub fn inner(&self) -> &StaticGrid<T> {
+        &self.grid
+    }
+
+    pub fn into_inner(self) -> StaticGrid<T> {
+        self.grid
+    }
+
+    /// The Point inside the grid that `point` wraps around to. Unchanged for an empty grid.
+    pub fn wrap(&self, point: Point) -> Point {
+        if self.grid.cells.is_empty() {
+            return point;
+        }
+        Point::new(
+            point.x.rem_euclid(self.grid.num_cols as isize),
+            point.y.rem_euclid(self.grid.num_rows as isize),
+        )
+    }
+
+    fn wrapped_neighbors(&self, point: Point, diagonals: bool) -> Vec<Point> {
+        let mut neighbors: Vec<Point> = Vec::with_capacity(8);
+        if self.grid.cells.is_empty() {
+            return neighbors;
+        }
+        for direction in enum_iterator::all::<CardinalDirection>() {
+            let offset = direction.offset();
+            if !diagonals && offset.x != 0 && offset.y != 0 {
+                continue;
+            }
+            let p = self.wrap(point + offset);
+            // On a grid 1 cell wide or tall, stepping off an edge can land back on `point`
+            if p != self.wrap(point) && !neighbors.contains(&p) {
+                neighbors.push(p);
+            }
+        }
+        neighbors
+    }
+}
+
+impl<T> WrappingGrid<T>
+where
+    T: Default + Clone,
+{
+    /// Returns a never ending iterator moving in the specified direction, starting at the x,y coord
+    pub fn direction_iter_at(
+        &self,
+        x: isize,
+        y: isize,
+        direction: CardinalDirection,
+    ) -> GridDirectionIter<'_, T> {
+        GridDirectionIter {
+            grid: self,
+            direction,
+            next_x: x,
+            next_y: y,
+        }
+    }
+
+    /// Calls `f` on `n` cells moving in the specified direction, starting at `start`. Cells are
+    /// visited again once the walk wraps all the way around.
+    pub fn for_each_in_direction_mut(
+        &mut self,
+        start: Point,
+        direction: CardinalDirection,
+        n: usize,
+        mut f: impl FnMut(&mut T),
+    ) {
+        if self.grid.cells.is_empty() {
+            return;
+        }
+        let mut point = start;
+        for _ in 0..n {
+            f(&mut self[point]);
+            point = self.wrap(point.step(direction, 1));
+        }
+    }
+}
+
+impl<T> Grid for WrappingGrid<T>
+where
+    T: Default + Clone,
+{
+    type Item = T;
+
+    fn get_cell(&self, x: isize, y: isize) -> Option<&Self::Item> {
+        self.grid.get(self.wrap(Point::new(x, y)))
+    }
+
+    fn get_cell_mut(&mut self, x: isize, y: isize) -> Option<&mut Self::Item> {
+        let point = self.wrap(Point::new(x, y));
+        self.grid.get_mut(point)
+    }
+
+    fn first_cell_coord(&self) -> Point {
+        self.grid.first_cell_coord()
+    }
+
+    fn last_cell_coord(&self) -> Point {
+        self.grid.last_cell_coord()
+    }
+
+    fn get_row(&self, y: isize) -> Option<&[Self::Item]> {
+        self.grid.get_row(self.wrap(Point::new(0, y)).y)
+    }
+
+    fn neighbors4(&self, point: Point) -> impl Iterator<Item = (Point, &Self::Item)>
+    where
+        Self: Sized,
+    {
+        self.wrapped_neighbors(point, false)
+            .into_iter()
+            .map(|p| (p, &self[p]))
+    }
+
+    fn neighbors8(&self, point: Point) -> impl Iterator<Item = (Point, &Self::Item)>
+    where
+        Self: Sized,
+    {
+        self.wrapped_neighbors(point, true)
+            .into_iter()
+            .map(|p| (p, &self[p]))
+    }
+
+    fn for_each_neighbor4_mut(&mut self, point: Point, mut f: impl FnMut(Point, &mut Self::Item))
+    where
+        Self: Sized,
+    {
+        for p in self.wrapped_neighbors(point, false) {
+            f(p, &mut self[p]);
+        }
+    }
+
+    fn for_each_neighbor8_mut(&mut self, point: Point, mut f: impl FnMut(Point, &mut Self::Item))
+    where
+        Self: Sized,
+    {
+        for p in self.wrapped_neighbors(point, true) {
+            f(p, &mut self[p]);
+        }
+    }
+}
+
+/// Panics only if the grid is empty
+impl<T> Index<Point> for WrappingGrid<T>
+where
+    T: Default + Clone,
+{
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        &self.grid[self.wrap(point)]
+    }
+}
+
+impl<T> IndexMut<Point> for WrappingGrid<T>
+where
+    T: Default + Clone,
+{
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        let point = self.wrap(point);
+        &mut self.grid[point]
+    }
+}
+
+/// True if a num_rows x num_cols window at `origin` is entirely inside `grid`
+fn window_fits<G: Grid>(grid: &G, origin: Point, num_rows: usize, num_cols: usize) -> bool {
+    if num_rows == 0 || num_cols == 0 {
+        return true;
+    }
+    let last = Point::new(
+        origin
+            .x
+            .checked_add_unsigned(num_cols - 1)
+            .unwrap_or(isize::MAX),
+        origin
+            .y
+            .checked_add_unsigned(num_rows - 1)
+            .unwrap_or(isize::MAX),
+    );
+    grid.contains(origin) && grid.contains(last)
+}
+
+/// A rectangular window into another grid, without copying
+///
+/// Coordinates are local to the window, so its top left cell is (0, 0) whatever the underlying
+/// grid uses. Build one with `Grid::view`.
+pub struct GridView<'a, G> {
+    grid: &'a G,
+    origin: Point,
+    num_rows: usize,
+    num_cols: usize,
+}
+
+impl<'a, G: Grid> GridView<'a, G> {
+    pub fn new(grid: &'a G, origin: Point, num_rows: usize, num_cols: usize) -> Option<Self> {
+        window_fits(grid, origin, num_rows, num_cols).then_some(GridView {
+            grid,
+            origin,
+            num_rows,
+            num_cols,
+        })
+    }
+
+    /// Where the window's (0, 0) is in the underlying grid
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+}
+
+impl<G> Clone for GridView<'_, G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G> Copy for GridView<'_, G> {}
+
+impl<G: Grid> Grid for GridView<'_, G> {
+    type Item = G::Item;
+
+    fn get_cell(&self, x: isize, y: isize) -> Option<&Self::Item> {
+        if self.contains(Point::new(x, y)) {
+            self.grid.get_cell(self.origin.x + x, self.origin.y + y)
+        } else {
+            None
+        }
+    }
+
+    /// Always None, the view is read only
+    fn get_cell_mut(&mut self, _x: isize, _y: isize) -> Option<&mut Self::Item> {
+        None
+    }
+
+    fn first_cell_coord(&self) -> Point {
+        Point::new(0, 0)
+    }
+
+    fn last_cell_coord(&self) -> Point {
+        Point::new(self.num_cols as isize - 1, self.num_rows as isize - 1)
+    }
+
+    fn get_row(&self, y: isize) -> Option<&[Self::Item]> {
+        view_row(self.grid, self.origin, self.num_cols, self.num_rows, y)
+    }
+}
+
+/// A rectangular window into another grid that can change its cells, see `GridView`
+pub struct GridViewMut<'a, G> {
+    grid: &'a mut G,
+    origin: Point,
+    num_rows: usize,
+    num_cols: usize,
+}
+
+impl<'a, G: Grid> GridViewMut<'a, G> {
+    pub fn new(grid: &'a mut G, origin: Point, num_rows: usize, num_cols: usize) -> Option<Self> {
+        window_fits(grid, origin, num_rows, num_cols).then_some(GridViewMut {
+            grid,
+            origin,
+            num_rows,
+            num_cols,
+        })
+    }
+
+    /// Where the window's (0, 0) is in the underlying grid
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+
+    /// A read only view of the same window
+    pub fn as_view(&self) -> GridView<'_, G> {
+        GridView {
+            grid: self.grid,
+            origin: self.origin,
+            num_rows: self.num_rows,
+            num_cols: self.num_cols,
+        }
+    }
+}
+
+impl<G: Grid> Grid for GridViewMut<'_, G> {
+    type Item = G::Item;
+
+    fn get_cell(&self, x: isize, y: isize) -> Option<&Self::Item> {
+        if self.contains(Point::new(x, y)) {
+            self.grid.get_cell(self.origin.x + x, self.origin.y + y)
+        } else {
+            None
+        }
+    }
+
+    fn get_cell_mut(&mut self, x: isize, y: isize) -> Option<&mut Self::Item> {
+        if self.contains(Point::new(x, y)) {
+            self.grid.get_cell_mut(self.origin.x + x, self.origin.y + y)
+        } else {
+            None
+        }
+    }
+
+    fn first_cell_coord(&self) -> Point {
+        Point::new(0, 0)
+    }
+
+    fn last_cell_coord(&self) -> Point {
+        Point::new(self.num_cols as isize - 1, self.num_rows as isize - 1)
+    }
+
+    fn get_row(&self, y: isize) -> Option<&[Self::Item]> {
+        view_row(self.grid, self.origin, self.num_cols, self.num_rows, y)
+    }
+}
+
+/// Row `y` of a window, cut out of the underlying grid's row
+fn view_row<G: Grid>(
+    grid: &G,
+    origin: Point,
+    num_cols: usize,
+    num_rows: usize,
+    y: isize,
+) -> Option<&[G::Item]> {
+    if y < 0 || y >= num_rows as isize {
+        return None;
+    }
+    let start = usize::try_from(origin.x - grid.first_cell_coord().x).ok()?;
+    grid.get_row(origin.y + y)?
+        .get(start..start.checked_add(num_cols)?)
+}
+
+/// Panics if the point is outside the view, use `get` to check
+impl<G: Grid> Index<Point> for GridView<'_, G> {
+    type Output = G::Item;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the view"))
+    }
+}
+
+impl<G: Grid> Index<Point> for GridViewMut<'_, G> {
+    type Output = G::Item;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the view"))
+    }
+}
+
+impl<G: Grid> IndexMut<Point> for GridViewMut<'_, G> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the view"))
+    }
+}
+
+/// Cell types that can be read from one character of a text grid
+///
+/// For a grid to round trip through `Display`, a cell has to display as the character it was
+/// read from.
+pub trait CellChar: Sized {
+    fn from_char(c: char) -> Option<Self>;
+}
+
+impl CellChar for char {
+    fn from_char(c: char) -> Option<Self> {
+        Some(c)
+    }
+}
+
+/// Reads a grid written one row per line, e.g. by `Display`
+impl<T> TryFrom<&str> for StaticGrid<T>
+where
+    T: CellChar,
+{
+    type Error = GridError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let rows = s
+            .lines()
+            .enumerate()
+            .map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(col, c)| T::from_char(c).ok_or(GridError::InvalidChar { row, col, c }))
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+        StaticGrid::from_rows(rows)
+    }
+}
+
+impl FromStr for StaticGrid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
 /*
     Enums
 */
-#[derive(Debug, PartialEq, Clone, Sequence, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum GridError {
+    /// A row didn't have the same number of cells as the first row
+    RaggedRow {
+        row: usize,
+        len: usize,
+        expected: usize,
+    },
+    /// The number of cells isn't a multiple of the number of columns
+    SizeMismatch { len: usize, num_cols: usize },
+    /// The cells can't all be addressed with a Point
+    TooLarge { num_rows: usize, num_cols: usize },
+    /// The cells of a 3D grid can't all be addressed with a Point3D
+    TooLarge3D {
+        size_x: usize,
+        size_y: usize,
+        size_z: usize,
+    },
+    /// A character in a text grid isn't a valid cell
+    InvalidChar { row: usize, col: usize, c: char },
+    /// The operation needs the same number of rows and columns
+    NotSquare { num_rows: usize, num_cols: usize },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::RaggedRow { row, len, expected } => {
+                write!(f, "row {row} has {len} cells, expected {expected}")
+            }
+            GridError::SizeMismatch { len, num_cols } => {
+                write!(f, "{len} cells can't be split into rows of {num_cols}")
+            }
+            GridError::TooLarge { num_rows, num_cols } => {
+                write!(f, "a {num_rows}x{num_cols} grid is too large")
+            }
+            GridError::TooLarge3D {
+                size_x,
+                size_y,
+                size_z,
+            } => {
+                write!(f, "a {size_x}x{size_y}x{size_z} grid is too large")
+            }
+            GridError::InvalidChar { row, col, c } => {
+                write!(f, "invalid cell {c:?} at row {row}, column {col}")
+            }
+            GridError::NotSquare { num_rows, num_cols } => {
+                write!(f, "a {num_rows}x{num_cols} grid isn't square")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Sequence, Copy)]
 pub enum CardinalDirection {
     North,
     NorthEast,
@@ -259,6 +1528,33 @@ pub enum RotateAmount {
 }
 
 impl CardinalDirection {
+    /// North, East, South and West
+    pub const ORTHOGONAL: [CardinalDirection; 4] = [
+        CardinalDirection::North,
+        CardinalDirection::East,
+        CardinalDirection::South,
+        CardinalDirection::West,
+    ];
+
+    pub const DIAGONAL: [CardinalDirection; 4] = [
+        CardinalDirection::NorthEast,
+        CardinalDirection::SouthEast,
+        CardinalDirection::SouthWest,
+        CardinalDirection::NorthWest,
+    ];
+
+    /// All 8 directions, clockwise from North
+    pub const ALL: [CardinalDirection; 8] = [
+        CardinalDirection::North,
+        CardinalDirection::NorthEast,
+        CardinalDirection::East,
+        CardinalDirection::SouthEast,
+        CardinalDirection::South,
+        CardinalDirection::SouthWest,
+        CardinalDirection::West,
+        CardinalDirection::NorthWest,
+    ];
+
     fn skip_multi(dir: &CardinalDirection, amt: usize) -> CardinalDirection {
         if amt > 0 {
             let mut new_dir = enum_iterator::next_cycle(dir);
@@ -270,6 +1566,20 @@ impl CardinalDirection {
         *dir
     }
 
+    /// The Point one step in this direction from (0, 0). North is -y.
+    pub fn offset(&self) -> Point {
+        match self {
+            CardinalDirection::North => Point::new(0, -1),
+            CardinalDirection::NorthEast => Point::new(1, -1),
+            CardinalDirection::East => Point::new(1, 0),
+            CardinalDirection::SouthEast => Point::new(1, 1),
+            CardinalDirection::South => Point::new(0, 1),
+            CardinalDirection::SouthWest => Point::new(-1, 1),
+            CardinalDirection::West => Point::new(-1, 0),
+            CardinalDirection::NorthWest => Point::new(-1, -1),
+        }
+    }
+
     pub fn rotate_by_angle(&self, amt: &RotateAmount) -> CardinalDirection {
         match amt {
             RotateAmount::_45 => CardinalDirection::skip_multi(self, 1),
@@ -288,6 +1598,31 @@ pub enum ClockDirection {
     CounterClockwise,
 }
 
+/// Which lines `StaticGrid::lines` walks
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Sequence)]
+pub enum LineOrientation {
+    /// West to East
+    Row,
+    /// North to South
+    Col,
+    /// North West to South East
+    Diagonal,
+    /// North East to South West
+    AntiDiagonal,
+}
+
+impl LineOrientation {
+    /// The direction the lines run in, reverse a line to go the opposite way
+    pub fn direction(&self) -> CardinalDirection {
+        match self {
+            LineOrientation::Row => CardinalDirection::East,
+            LineOrientation::Col => CardinalDirection::South,
+            LineOrientation::Diagonal => CardinalDirection::SouthEast,
+            LineOrientation::AntiDiagonal => CardinalDirection::SouthWest,
+        }
+    }
+}
+
 /*
     Structs
 */
@@ -391,13 +1726,120 @@ where
                     }
                 }
 
-                unsafe { Some(&mut *(item as *mut T)) }
-            }
-            None => None,
+                unsafe { Some(&mut *(item as *mut T)) }
+            }
+            None => None,
+        }
+    }
+}
+
+/// One straight line of StaticGrid cells with their coordinates, see `StaticGrid::lines`
+#[derive(Clone)]
+pub struct GridLine<'a, T> {
+    cells: &'a [T],
+    num_cols: usize,
+    start: Point,
+    step: Point,
+    len: usize,
+}
+
+impl<'a, T> GridLine<'a, T> {
+    fn cell(&self, n: usize) -> (Point, &'a T) {
+        let p = self.start + self.step * n as isize;
+        (p, &self.cells[p.y as usize * self.num_cols + p.x as usize])
+    }
+}
+
+impl<'a, T> Iterator for GridLine<'a, T> {
+    type Item = (Point, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let cell = self.cell(0);
+        self.start += self.step;
+        self.len -= 1;
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for GridLine<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
         }
+        self.len -= 1;
+        Some(self.cell(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for GridLine<'_, T> {}
+
+impl<T> FusedIterator for GridLine<'_, T> {}
+
+/// The cells of one StaticGrid column, without allocating
+#[derive(Clone)]
+pub struct ColIter<'a, T>(StepBy<slice::Iter<'a, T>>);
+
+impl<'a, T> Iterator for ColIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.nth(n)
+    }
+}
+
+impl<T> DoubleEndedIterator for ColIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for ColIter<'_, T> {}
+
+impl<T> FusedIterator for ColIter<'_, T> {}
+
+pub struct ColIterMut<'a, T>(StepBy<slice::IterMut<'a, T>>);
+
+impl<'a, T> Iterator for ColIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.nth(n)
+    }
+}
+
+impl<T> DoubleEndedIterator for ColIterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
     }
 }
 
+impl<T> ExactSizeIterator for ColIterMut<'_, T> {}
+
+impl<T> FusedIterator for ColIterMut<'_, T> {}
+
 /* Iterates over all of the cells surrounding a center cell
  * Does not return center cell
  **/
@@ -566,6 +2008,9 @@ impl Iterator for GridCoordinateIter {
     type Item = Point;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.next_point.y > self.end.y || self.start.x > self.end.x {
+            return None;
+        }
         let this_point = Point {
             x: self.next_point.x,
             y: self.next_point.y,
@@ -575,9 +2020,6 @@ impl Iterator for GridCoordinateIter {
             self.next_point.y += 1;
             self.next_point.x = self.start.x;
         }
-        if self.next_point.y > self.end.y {
-            return None;
-        }
         Some(this_point)
     }
 }
@@ -775,6 +2217,29 @@ where
     }
 }
 
+/// Panics if the point hasn't been added to the grid yet, use `get` to check
+impl<CellType> Index<Point> for DynamicGrid<CellType>
+where
+    CellType: Default + Clone,
+{
+    type Output = CellType;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the grid"))
+    }
+}
+
+impl<CellType> IndexMut<Point> for DynamicGrid<CellType>
+where
+    CellType: Default + Clone,
+{
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the grid"))
+    }
+}
+
 impl<CellType> Growable for DynamicGrid<CellType>
 where
     CellType: Default + Clone,
@@ -847,13 +2312,184 @@ where
     }
 }
 
-#[derive(Clone, Default, Debug)]
+/// A 2D coordinate with any numeric type, e.g. i64 for huge puzzles or u16 for compact storage
+///
+/// Converts to and from `Point` (checked) and `(x, y)` tuples.
+#[derive(Clone, Default, Debug, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Point2D<CoordType> {
     pub x: CoordType,
     pub y: CoordType,
 }
 
-#[derive(Clone, Default, Debug, Copy)]
+impl<CoordType> Point2D<CoordType>
+where
+    CoordType: Copy,
+{
+    pub fn new(x: CoordType, y: CoordType) -> Self {
+        Point2D { x, y }
+    }
+
+    /// (x, y) as usize indices, None if either is negative or doesn't fit
+    pub fn to_indices(&self) -> Option<(usize, usize)>
+    where
+        CoordType: TryInto<usize>,
+    {
+        Some((self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+
+    /// Index into cells stored row by row, None if the point isn't within `num_cols` columns
+    pub fn to_index(&self, num_cols: usize) -> Option<usize>
+    where
+        CoordType: TryInto<usize>,
+    {
+        let (x, y) = self.to_indices()?;
+        if x >= num_cols {
+            return None;
+        }
+        y.checked_mul(num_cols)?.checked_add(x)
+    }
+
+    /// Distance moving only orthogonally
+    pub fn manhattan(&self, other: Self) -> CoordType
+    where
+        CoordType: Sub<Output = CoordType> + Add<Output = CoordType> + PartialOrd,
+    {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Distance moving orthogonally or diagonally (king moves)
+    pub fn chebyshev(&self, other: Self) -> CoordType
+    where
+        CoordType: Sub<Output = CoordType> + PartialOrd,
+    {
+        let (dx, dy) = (abs_diff(self.x, other.x), abs_diff(self.y, other.y));
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+
+    /// Returns the point `n` steps away in a cardinal direction (signed types only)
+    pub fn step(&self, direction: CardinalDirection, n: CoordType) -> Self
+    where
+        CoordType: From<i8> + Add<Output = CoordType> + Mul<Output = CoordType>,
+    {
+        let offset = direction.offset();
+        // Offsets are always -1, 0 or 1
+        Point2D::new(
+            self.x + CoordType::from(offset.x as i8) * n,
+            self.y + CoordType::from(offset.y as i8) * n,
+        )
+    }
+}
+
+fn abs_diff<T: Sub<Output = T> + PartialOrd>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<CoordType: Add<Output = CoordType>> Add for Point2D<CoordType> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point2D {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl<CoordType: AddAssign> AddAssign for Point2D<CoordType> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<CoordType: Sub<Output = CoordType>> Sub for Point2D<CoordType> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point2D {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<CoordType: SubAssign> SubAssign for Point2D<CoordType> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<CoordType: Neg<Output = CoordType>> Neg for Point2D<CoordType> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point2D {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<CoordType: Mul<Output = CoordType> + Copy> Mul<CoordType> for Point2D<CoordType> {
+    type Output = Self;
+
+    fn mul(self, rhs: CoordType) -> Self::Output {
+        Point2D {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl<CoordType> From<(CoordType, CoordType)> for Point2D<CoordType> {
+    fn from((x, y): (CoordType, CoordType)) -> Self {
+        Point2D { x, y }
+    }
+}
+
+impl<CoordType> From<Point2D<CoordType>> for (CoordType, CoordType) {
+    fn from(point: Point2D<CoordType>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+/// Fails if a coordinate doesn't fit in CoordType
+impl<CoordType: TryFrom<isize>> TryFrom<Point> for Point2D<CoordType> {
+    type Error = CoordType::Error;
+
+    fn try_from(point: Point) -> Result<Self, Self::Error> {
+        Ok(Point2D {
+            x: point.x.try_into()?,
+            y: point.y.try_into()?,
+        })
+    }
+}
+
+/// Fails if a coordinate doesn't fit in an isize
+impl<CoordType> TryFrom<Point2D<CoordType>> for Point
+where
+    isize: TryFrom<CoordType>,
+{
+    type Error = <isize as TryFrom<CoordType>>::Error;
+
+    fn try_from(point: Point2D<CoordType>) -> Result<Self, Self::Error> {
+        Ok(Point {
+            x: point.x.try_into()?,
+            y: point.y.try_into()?,
+        })
+    }
+}
+
+#[derive(Clone, Default, Debug, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Point {
     pub x: isize,
     pub y: isize,
@@ -864,47 +2500,94 @@ impl Point {
         Point { x, y }
     }
 
-    /// Add an amount to the Point based on a cardinal direction
-    pub fn add(&mut self, amt: isize, direction: CardinalDirection) {
-        match direction {
-            CardinalDirection::North => self.y -= amt,
-            CardinalDirection::South => self.y += amt,
-            CardinalDirection::East => self.x += amt,
-            CardinalDirection::West => self.x -= amt,
-            CardinalDirection::NorthEast => {
-                self.x += amt;
-                self.y -= amt
-            }
-            CardinalDirection::NorthWest => {
-                self.x -= amt;
-                self.y -= amt
-            }
-            CardinalDirection::SouthEast => {
-                self.x += amt;
-                self.y += amt
-            }
-            CardinalDirection::SouthWest => {
-                self.x -= amt;
-                self.y -= amt
-            }
-        };
+    /// Moves the Point `n` steps in a cardinal direction, see `step` for a new Point instead
+    pub fn step_in_place(&mut self, direction: CardinalDirection, n: isize) {
+        *self = self.step(direction, n);
+    }
+
+    /// Returns the Point `n` steps away in a cardinal direction
+    pub fn step(&self, direction: CardinalDirection, n: isize) -> Point {
+        *self + direction.offset() * n
+    }
+
+    /// Distance moving only orthogonally
+    pub fn manhattan(&self, other: Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Distance moving orthogonally or diagonally (king moves)
+    pub fn chebyshev(&self, other: Point) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Self::Output {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Point::new(self.x * rhs, self.y * rhs)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fmt::Display;
+    use std::{fmt::Display, ops::Add};
 
-    use crate::{CardinalDirection, DynamicGrid, Growable, StaticGrid};
+    use crate::{
+        CardinalDirection, CellChar, Connectivity, DynamicGrid, Grid, GridError, Growable,
+        LineOrientation, Point, Point2D, StaticGrid, WrappingGrid,
+    };
 
     /*
     Test Structs
     */
-    #[derive(Clone)]
+    #[derive(Clone, Debug, PartialEq)]
     struct TestCell {
         value: char,
     }
 
+    impl CellChar for TestCell {
+        fn from_char(c: char) -> Option<Self> {
+            "a.#".contains(c).then_some(TestCell { value: c })
+        }
+    }
+
     impl Default for TestCell {
         fn default() -> Self {
             Self { value: '.' }
@@ -933,6 +2616,52 @@ mod tests {
         assert_eq!(i.next().unwrap().value, 'a');
     }
 
+    #[test]
+    fn test_grid_constructors() {
+        let g = StaticGrid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!((g.num_rows(), g.num_cols()), (2, 3));
+        assert_eq!(g.row(1), &[4, 5, 6]);
+        assert_eq!(
+            StaticGrid::from_rows(vec![vec![1, 2], vec![3]]).unwrap_err(),
+            GridError::RaggedRow {
+                row: 1,
+                len: 1,
+                expected: 2
+            }
+        );
+
+        let g = StaticGrid::from_vec(vec![1, 2, 3, 4, 5, 6], 2).unwrap();
+        assert_eq!((g.num_rows(), g.num_cols()), (3, 2));
+        assert!(StaticGrid::from_vec(vec![1, 2, 3], 2).is_err());
+        assert!(StaticGrid::from_vec(vec![1], 0).is_err());
+        assert_eq!(StaticGrid::<u8>::from_vec(vec![], 0).unwrap().num_rows(), 0);
+
+        let g = StaticGrid::from_fn(2, 3, |p| p.x + p.y * 10).unwrap();
+        assert_eq!(g.cells(), &[0, 1, 2, 10, 11, 12]);
+        assert!(StaticGrid::from_fn(usize::MAX, 2, |_| 0).is_err());
+    }
+
+    #[test]
+    fn test_grid_from_text() {
+        let text = "ab.\n#.a\n";
+        let g: StaticGrid<char> = text.parse().unwrap();
+        assert_eq!((g.num_rows(), g.num_cols()), (2, 3));
+        assert_eq!(g.to_string(), text);
+
+        let mut g = StaticGrid::<TestCell>::try_from("a..\r\n.#.").unwrap();
+        g.row_mut(1)[2].value = 'a';
+        assert_eq!(g, StaticGrid::try_from("a..\n.#a").unwrap());
+        assert_eq!(g.to_string(), "a..\n.#a\n");
+        assert_eq!(
+            StaticGrid::<TestCell>::try_from("a.\n.b").unwrap_err(),
+            GridError::InvalidChar {
+                row: 1,
+                col: 1,
+                c: 'b'
+            }
+        );
+    }
+
     #[test]
     fn test_dynamic_grid() {
         let mut g = DynamicGrid::<TestCell>::new(500, 0);
@@ -948,6 +2677,342 @@ mod tests {
         assert_eq!(g.num_cols, 5);
     }
 
+    #[test]
+    fn test_point_indexing() {
+        let mut g: StaticGrid<char> = "ab\ncd".parse().unwrap();
+        assert_eq!(g[Point::new(1, 0)], 'b');
+        g[Point::new(0, 1)] = 'x';
+        assert_eq!(g.get(Point::new(0, 1)), Some(&'x'));
+        assert_eq!(g.get(Point::new(2, 0)), None);
+        assert_eq!(g.get_mut(Point::new(-1, 0)), None);
+
+        let mut g = DynamicGrid::<TestCell>::new(500, 0);
+        g.get_cell_or_add_mut(498, -2).value = 'a';
+        assert_eq!(g[Point::new(498, -2)].value, 'a');
+        g[Point::new(500, 0)].value = '#';
+        assert_eq!(g.get_cell(500, 0).unwrap().value, '#');
+        assert!(g.get(Point::new(501, 0)).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_point_indexing_out_of_bounds() {
+        let g = StaticGrid::<u8>::new(2, 2);
+        let _ = g[Point::new(2, 2)];
+    }
+
+    #[test]
+    #[should_panic(expected = "too large")]
+    fn test_grid_new_too_large() {
+        let _ = StaticGrid::<()>::new(usize::MAX, 2);
+    }
+
+    #[test]
+    fn test_grid_coords() {
+        let g: StaticGrid<char> = "abc\ndef".parse().unwrap();
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert!(g.contains(Point::new(2, 1)));
+        assert!(!g.contains(Point::new(3, 1)));
+        assert!(!g.contains(Point::new(0, -1)));
+        assert_eq!(g.coords().count(), 6);
+        assert_eq!(g.iter_with_coords().last(), Some((Point::new(2, 1), &'f')));
+        assert_eq!(StaticGrid::<char>::new(0, 0).coords().count(), 0);
+
+        let mut g = DynamicGrid::<TestCell>::new(500, 0);
+        g.get_cell_or_add_mut(499, -1).value = 'a';
+        g.get_cell_or_add_mut(501, 0);
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert!(g.contains(Point::new(499, -1)));
+        assert!(!g.contains(Point::new(500, 1)));
+        let cells: String = g.iter_with_coords().map(|(_, c)| c.value).collect();
+        assert_eq!(cells, "a.....");
+        assert_eq!(g.coords().next(), Some(Point::new(499, -1)));
+    }
+
+    #[test]
+    fn test_grid_search() {
+        let g: StaticGrid<char> = "X.A\nAX.".parse().unwrap();
+        assert_eq!(g.position(|&c| c == 'A'), Some(Point::new(2, 0)));
+        assert_eq!(g.position(|&c| c == 'S'), None);
+        assert_eq!(
+            g.positions(|&c| c == 'X').collect::<Vec<_>>(),
+            vec![Point::new(0, 0), Point::new(1, 1)]
+        );
+        assert_eq!(g.count(|&c| c != '.'), 4);
+
+        // DynamicGrid reports positions in its own (offset) coordinates
+        let mut g = DynamicGrid::<TestCell>::new(500, 0);
+        g.get_cell_or_add_mut(498, -2).value = 'a';
+        g.get_cell_or_add_mut(501, 1).value = 'a';
+        assert_eq!(
+            g.positions(|c| c.value == 'a').collect::<Vec<_>>(),
+            vec![Point::new(498, -2), Point::new(501, 1)]
+        );
+        assert_eq!(g.count(|c| c.value == '.'), 14);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let mut g: StaticGrid<char> = "abc\ndef\nghi".parse().unwrap();
+        let n: String = g.neighbors8(Point::new(1, 1)).map(|(_, c)| c).collect();
+        assert_eq!(n, "bcfihgda");
+        let n: String = g.neighbors4(Point::new(1, 1)).map(|(_, c)| c).collect();
+        assert_eq!(n, "bfhd");
+        assert_eq!(
+            g.neighbors4(Point::new(0, 0)).collect::<Vec<_>>(),
+            vec![(Point::new(1, 0), &'b'), (Point::new(0, 1), &'d')]
+        );
+        assert_eq!(g.neighbors8(Point::new(2, 2)).count(), 3);
+
+        g.for_each_neighbor4_mut(Point::new(0, 0), |_, c| *c = '#');
+        g.for_each_neighbor8_mut(Point::new(2, 2), |p, c| {
+            if p.x != 2 && p.y != 2 {
+                *c = '*';
+            }
+        });
+        assert_eq!(g.to_string(), "a#c\n#*f\nghi\n");
+    }
+
+    #[test]
+    fn test_grid_transforms() {
+        let g: StaticGrid<char> = "abc\ndef".parse().unwrap();
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(g.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(g.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(g.rotate_180().to_string(), "fed\ncba\n");
+        assert_eq!(g.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(g.flip_vertical().to_string(), "def\nabc\n");
+        assert_eq!(g.rotate_cw().rotate_cw(), g.rotate_180());
+
+        let mut non_square = g.clone();
+        assert_eq!(
+            non_square.rotate_cw_in_place(),
+            Err(GridError::NotSquare {
+                num_rows: 2,
+                num_cols: 3
+            })
+        );
+        assert_eq!(non_square, g);
+
+        let mut square: StaticGrid<char> = "abc\ndef\nghi".parse().unwrap();
+        let symmetries: Vec<_> = square.symmetries().collect();
+        assert_eq!(symmetries.len(), 8);
+        for (i, a) in symmetries.iter().enumerate() {
+            assert!(!symmetries[i + 1..].contains(a));
+        }
+        assert!(symmetries.contains(&square.flip_vertical()));
+        square.rotate_cw_in_place().unwrap();
+        assert_eq!(square, symmetries[1]);
+        square.rotate_ccw_in_place().unwrap();
+        square.rotate_ccw_in_place().unwrap();
+        assert_eq!(square, symmetries[3]);
+        square.flip_vertical_in_place();
+        assert_eq!(square.to_string(), "adg\nbeh\ncfi\n");
+        square.flip_horizontal_in_place();
+        assert_eq!(square, symmetries[1]);
+    }
+
+    #[test]
+    fn test_grid_view() {
+        let mut g: StaticGrid<char> = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM".parse().unwrap();
+        assert!(g.view(Point::new(8, 0), 3, 3).is_none());
+        assert!(g.view(Point::new(-1, 0), 1, 1).is_none());
+        // A window starting left of the grid has no rows
+        assert_eq!(super::view_row(&g, Point::new(-1, 0), 2, 1, 0), None);
+
+        let view = g.view(Point::new(1, 0), 3, 3).unwrap();
+        assert_eq!((view.width(), view.height()), (3, 3));
+        assert_eq!(view[Point::new(0, 0)], 'M');
+        assert_eq!(view.get(Point::new(3, 0)), None);
+        assert_eq!(view.get_row(1), Some(&['S', 'A', 'M'][..]));
+        assert_eq!(view.position(|&c| c == 'A'), Some(Point::new(1, 1)));
+        assert_eq!(view.neighbors8(Point::new(1, 1)).count(), 8);
+        // The same 3x3 patch compared through two views
+        let other = g.view(Point::new(1, 0), 3, 3).unwrap();
+        assert!(view.coords().all(|p| view[p] == other[p]));
+
+        let mut view = g.view_mut(Point::new(7, 1), 2, 3).unwrap();
+        assert_eq!(view.as_view().origin(), Point::new(7, 1));
+        view[Point::new(2, 1)] = '#';
+        view.for_each_neighbor4_mut(Point::new(0, 0), |_, c| *c = '.');
+        assert_eq!(view.get_mut(Point::new(0, 2)), None);
+        assert_eq!(g.to_string(), "MMMSXXMASM\nMSAMXMSM.A\nAMXSXMA.M#\n");
+    }
+
+    #[test]
+    fn test_grid_cols() {
+        let mut g: StaticGrid<char> = "abc\ndef".parse().unwrap();
+        assert_eq!(g.col(1).collect::<String>(), "be");
+        assert_eq!(g.col(2).rev().collect::<String>(), "fc");
+        assert_eq!(g.col(0).len(), 2);
+        let mut col = g.col(0);
+        col.next();
+        assert_eq!(col.len(), 1);
+        assert_eq!(col.next_back(), Some(&'d'));
+        assert_eq!(col.next(), None);
+
+        assert_eq!(
+            g.cols().rev().map(|c| c.collect()).collect::<Vec<String>>(),
+            vec!["cf", "be", "ad"]
+        );
+        assert_eq!(g.cols().len(), 3);
+
+        for c in g.col_mut(2) {
+            *c = '#';
+        }
+        if let Some(c) = g.col_mut(0).next_back() {
+            *c = '.';
+        }
+        assert_eq!(g.to_string(), "ab#\n.e#\n");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_grid_col_outside() {
+        let g: StaticGrid<char> = "abc\ndef".parse().unwrap();
+        g.col(3);
+    }
+
+    #[test]
+    fn test_grid_lines() {
+        let g: StaticGrid<char> = "abc\ndef".parse().unwrap();
+        let lines = |orientation| -> Vec<String> {
+            g.lines(orientation)
+                .map(|line| line.map(|(_, c)| c).collect())
+                .collect()
+        };
+        assert_eq!(lines(LineOrientation::Row), vec!["abc", "def"]);
+        assert_eq!(lines(LineOrientation::Col), vec!["ad", "be", "cf"]);
+        assert_eq!(lines(LineOrientation::Diagonal), vec!["d", "ae", "bf", "c"]);
+        assert_eq!(
+            lines(LineOrientation::AntiDiagonal),
+            vec!["a", "bd", "ce", "f"]
+        );
+
+        let mut line = g.lines(LineOrientation::AntiDiagonal).nth(2).unwrap();
+        assert_eq!(line.len(), 2);
+        assert_eq!(line.next_back(), Some((Point::new(1, 1), &'e')));
+        assert_eq!(line.next(), Some((Point::new(2, 0), &'c')));
+        assert_eq!(line.next(), None);
+
+        // Every cell is on exactly one line of each orientation
+        for orientation in enum_iterator::all::<LineOrientation>() {
+            assert_eq!(g.lines(orientation).flatten().count(), 6);
+        }
+        let empty = StaticGrid::<char>::from_vec(vec![], 0).unwrap();
+        assert_eq!(empty.lines(LineOrientation::Diagonal).count(), 0);
+    }
+
+    #[test]
+    fn test_regions() {
+        let g: StaticGrid<char> = "AAB\nABB\nBAB".parse().unwrap();
+        let region = g.flood_fill(Point::new(2, 2), Connectivity::Four, |a, b| a == b);
+        assert_eq!(
+            region,
+            vec![
+                Point::new(2, 2),
+                Point::new(2, 1),
+                Point::new(2, 0),
+                Point::new(1, 1)
+            ]
+        );
+        assert!(g
+            .flood_fill(Point::new(3, 0), Connectivity::Four, |a, b| a == b)
+            .is_empty());
+
+        let components = g.label_components(Connectivity::Four, |a, b| a == b);
+        assert_eq!(components.len(), 4);
+        assert_eq!(components.regions[0].len(), 3);
+        assert_eq!(components.label(Point::new(0, 2)), Some(2));
+        assert_eq!(components.label(Point::new(1, 2)), Some(3));
+
+        // The lone B and A join the others diagonally
+        let components = g.label_components(Connectivity::Eight, |a, b| a == b);
+        assert_eq!(components.len(), 2);
+        assert_eq!(components.label(Point::new(1, 2)), Some(0));
+        assert_eq!(components.regions[1].len(), 5);
+    }
+
+    #[test]
+    fn test_bfs() {
+        let g: StaticGrid<char> = "S..#\n.#..\n...E\n##.#".parse().unwrap();
+        let start = Point::new(0, 0);
+        let end = Point::new(3, 2);
+        let paths = g.bfs(start, Connectivity::Four, |&c| c != '#');
+        assert_eq!(paths.distance(end), Some(5));
+        assert_eq!(paths.distance(Point::new(3, 0)), None);
+        assert_eq!(paths.distance(Point::new(2, 3)), Some(5));
+        assert_eq!(paths.distances().count(), 11);
+        // Around either side of the wall in the middle
+        assert_eq!(paths.count_paths(end), 3);
+        assert_eq!(paths.count_paths(Point::new(3, 0)), 0);
+
+        let path = paths.path_to(end).unwrap();
+        assert_eq!(path.len(), 6);
+        assert_eq!((path[0], path[5]), (start, end));
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+        assert_eq!(paths.path_to(start), Some(vec![start]));
+
+        let paths = g.bfs(start, Connectivity::Eight, |&c| c != '#');
+        assert_eq!(paths.distance(end), Some(3));
+        assert_eq!(paths.count_paths(end), 1);
+        assert_eq!(paths.distance(Point::new(3, 1)), Some(3));
+    }
+
+    #[test]
+    fn test_wrapping_grid() {
+        let mut g = WrappingGrid::new("abc\ndef".parse::<StaticGrid<char>>().unwrap());
+        assert_eq!(g.wrap(Point::new(-1, -1)), Point::new(2, 1));
+        assert_eq!(g[Point::new(-1, 0)], 'c');
+        assert_eq!(g[Point::new(4, -3)], 'e');
+        assert_eq!(g.get_cell(-7, 5), Some(&'f'));
+        assert!(g.contains(Point::new(2, 1)));
+        assert!(!g.contains(Point::new(100, -100)));
+        assert!(g.view(Point::new(-1, 0), 2, 4).is_none());
+        assert!(g.view(Point::new(0, 0), 2, 3).is_some());
+
+        let s: String = g
+            .direction_iter_at(1, 0, CardinalDirection::West)
+            .take(5)
+            .collect();
+        assert_eq!(s, "bacba");
+        let s: String = g
+            .direction_iter_at(0, 0, CardinalDirection::SouthEast)
+            .take(4)
+            .collect();
+        assert_eq!(s, "aecd");
+        let mut n = 0;
+        g.for_each_in_direction_mut(Point::new(2, 1), CardinalDirection::East, 7, |c| {
+            if *c == 'd' {
+                n += 1;
+            }
+        });
+        assert_eq!(n, 2);
+
+        // Only 2 rows, so North and South are the same cell
+        assert_eq!(
+            g.neighbors4(Point::new(0, 0)).collect::<Vec<_>>(),
+            vec![
+                (Point::new(0, 1), &'d'),
+                (Point::new(1, 0), &'b'),
+                (Point::new(2, 0), &'c')
+            ]
+        );
+        // Every other cell touches the corner
+        assert_eq!(g.neighbors8(Point::new(0, 0)).count(), 5);
+        g.for_each_neighbor8_mut(Point::new(0, 0), |_, c| *c = '.');
+        g[Point::new(3, 0)] = '#';
+        assert_eq!(g.into_inner().to_string(), "#..\n...\n");
+
+        // A single column is never its own neighbour
+        let g = WrappingGrid::new("a\nb\nc".parse::<StaticGrid<char>>().unwrap());
+        assert_eq!(
+            g.neighbors4(Point::new(0, 0)).collect::<Vec<_>>(),
+            vec![(Point::new(0, 2), &'c'), (Point::new(0, 1), &'b')]
+        );
+        assert_eq!(g.neighbors8(Point::new(0, 1)).count(), 2);
+    }
+
     #[test]
     fn test_iterator() {
         let mut g = DynamicGrid::<TestCell>::new(500, 0);
@@ -984,6 +3049,59 @@ mod tests {
         assert_eq!(s, "*....W");
     }
 
+    #[test]
+    fn test_point_math() {
+        let a = Point::new(1, 2);
+        let b = Point::new(-3, 5);
+        assert_eq!(a + b, Point::new(-2, 7));
+        // The method syntax is the operator too, not a mutating inherent method
+        assert_eq!(Add::add(a, b), a.add(b));
+        assert_eq!(a - b, Point::new(4, -3));
+        assert_eq!(-a, Point::new(-1, -2));
+        assert_eq!(b * 2, Point::new(-6, 10));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+
+        assert_eq!(a.step(CardinalDirection::SouthWest, 2), Point::new(-1, 4));
+        let mut c = a;
+        c.step_in_place(CardinalDirection::SouthWest, 2);
+        assert_eq!(c, a.step(CardinalDirection::SouthWest, 2));
+        c += b;
+        c -= b;
+        assert_eq!(c, Point::new(-1, 4));
+
+        let visited: std::collections::HashSet<Point> = [a, b, a].into_iter().collect();
+        assert_eq!(visited.len(), 2);
+        let mut sorted = vec![a, b, Point::new(1, 0)];
+        sorted.sort();
+        assert_eq!(sorted, vec![b, Point::new(1, 0), a]);
+    }
+
+    #[test]
+    fn test_point2d() {
+        let a = Point2D::<i64>::new(10_000_000_000, -2);
+        let b = Point2D::from((1, 3));
+        assert_eq!(a + b, Point2D::new(10_000_000_001, 1));
+        assert_eq!(-(a - b) * 2, Point2D::new(-19_999_999_998, 10));
+        assert_eq!(a.manhattan(b), 10_000_000_004);
+        assert_eq!(b.step(CardinalDirection::NorthWest, 3), Point2D::new(-2, 0));
+        assert_eq!(<(i64, i64)>::from(b), (1, 3));
+
+        let c = Point2D::<u16>::new(3, 9);
+        assert_eq!(c.chebyshev(Point2D::new(5, 1)), 8);
+        assert_eq!(c.to_indices(), Some((3, 9)));
+        assert_eq!(c.to_index(4), Some(39));
+        assert_eq!(c.to_index(3), None);
+        assert_eq!(Point2D::new(-1i32, 0).to_indices(), None);
+
+        assert_eq!(Point::try_from(c), Ok(Point::new(3, 9)));
+        assert_eq!(
+            Point2D::<u16>::try_from(Point::new(1, 2)),
+            Ok(Point2D::new(1, 2))
+        );
+        assert!(Point2D::<u16>::try_from(Point::new(-1, 2)).is_err());
+    }
+
     #[test]
     fn test_rotating_cardinal_direction() {
         assert_eq!(CardinalDirection::North.rotate_by_angle(&crate::RotateAmount::_45), CardinalDirection::NorthEast);
@@ -991,4 +3109,29 @@ mod tests {
         assert_eq!(CardinalDirection::South.rotate_by_angle(&crate::RotateAmount::_90), CardinalDirection::West);
         assert_eq!(CardinalDirection::South.rotate_by_angle(&crate::RotateAmount::_180), CardinalDirection::North);
     }
+
+    #[test]
+    fn test_puzzle_lines() {
+        fn num_pair(input: &str) -> nom::IResult<&str, Vec<u32>> {
+            crate::parsers::separated_integers(nom::character::complete::space1)(input)
+        }
+
+        let input = "3   4\r\n4   3\n\n2   5\n";
+        let lines = crate::PuzzleLines::new(input.as_bytes(), num_pair);
+        assert_eq!(
+            lines.collect::<Vec<_>>(),
+            vec![vec![3, 4], vec![4, 3], vec![2, 5]]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2")]
+    fn test_puzzle_lines_leftover() {
+        fn num_pair(input: &str) -> nom::IResult<&str, Vec<u32>> {
+            crate::parsers::separated_integers(nom::character::complete::space1)(input)
+        }
+
+        let input = "3   4  \n3   4 garbage\n";
+        crate::PuzzleLines::new(input.as_bytes(), num_pair).for_each(drop);
+    }
 }
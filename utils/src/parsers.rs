@@ -0,0 +1,125 @@
+//! Generic nom parsers for the number lists that show up in most puzzle inputs
+//!
+//! All of these work for any integer type (8 to 128 bits, signed or unsigned). A number that
+//! doesn't fit in the requested type is a `nom::Err::Failure`, so it isn't silently swallowed by
+//! `separated_list1` and friends.
+use std::str::FromStr;
+
+use nom::{
+    character::complete::{char, digit1, line_ending, not_line_ending},
+    combinator::{opt, recognize},
+    error::{Error, ErrorKind},
+    multi::separated_list1,
+    sequence::pair,
+    IResult,
+};
+
+/// Integer types that can be parsed from puzzle input
+pub trait PuzzleInt: FromStr + Copy {
+    const SIGNED: bool;
+}
+
+macro_rules! impl_puzzle_int {
+    ($signed:expr; $($t:ty),*) => {
+        $(impl PuzzleInt for $t {
+            const SIGNED: bool = $signed;
+        })*
+    };
+}
+
+impl_puzzle_int!(false; u8, u16, u32, u64, u128, usize);
+impl_puzzle_int!(true; i8, i16, i32, i64, i128, isize);
+
+/// Parses a single integer, with a leading '-' for signed types
+pub fn integer<T: PuzzleInt>(input: &str) -> IResult<&str, T> {
+    let (rest, digits) = if T::SIGNED {
+        recognize(pair(opt(char('-')), digit1))(input)?
+    } else {
+        digit1(input)?
+    };
+    match digits.parse::<T>() {
+        Ok(value) => Ok((rest, value)),
+        Err(_) => Err(nom::Err::Failure(Error::new(input, ErrorKind::TooLarge))),
+    }
+}
+
+/// Parses one or more integers separated by `sep`, e.g. `separated_integers(tag(","))`
+pub fn separated_integers<'a, T, O, S>(sep: S) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>>
+where
+    T: PuzzleInt,
+    S: Fn(&'a str) -> IResult<&'a str, O>,
+{
+    move |input| separated_list1(&sep, integer)(input)
+}
+
+/// Parses lines of integers separated by `sep`, one `Vec` per line
+pub fn integer_lines<'a, T, O, S>(sep: S) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Vec<T>>>
+where
+    T: PuzzleInt,
+    S: Fn(&'a str) -> IResult<&'a str, O>,
+{
+    move |input| separated_list1(line_ending, |line| separated_list1(&sep, integer)(line))(input)
+}
+
+/// Pulls every integer out of the rest of the line, ignoring anything in between
+///
+/// Stops before the line ending. For signed types a '-' directly in front of a number makes it
+/// negative, for unsigned types it is skipped like any other character.
+pub fn scan_integers<T: PuzzleInt>(input: &str) -> IResult<&str, Vec<T>> {
+    let (rest, mut line) = not_line_ending(input)?;
+    let mut values = Vec::new();
+    while let Some(c) = line.chars().next() {
+        match integer(line) {
+            Ok((remaining, value)) => {
+                values.push(value);
+                line = remaining;
+            }
+            Err(nom::Err::Error(_)) => line = &line[c.len_utf8()..],
+            Err(e) => return Err(e),
+        }
+    }
+    Ok((rest, values))
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{bytes::complete::tag, character::complete::space1};
+
+    use super::{integer, integer_lines, scan_integers, separated_integers};
+
+    #[test]
+    fn test_integer_parsers() {
+        assert_eq!(integer::<i8>("-128,"), Ok((",", -128)));
+        assert!(matches!(integer::<i8>("128"), Err(nom::Err::Failure(_))));
+        assert!(matches!(integer::<u32>("-1"), Err(nom::Err::Error(_))));
+        assert_eq!(
+            integer::<u128>("340282366920938463463374607431768211455"),
+            Ok(("", u128::MAX))
+        );
+
+        assert_eq!(
+            separated_integers::<u16, _, _>(tag(","))("75,47,61\n"),
+            Ok(("\n", vec![75, 47, 61]))
+        );
+        // Overflow in the middle of a list is an error, not the end of the list
+        assert!(separated_integers::<u8, _, _>(tag(","))("1,2,300").is_err());
+
+        assert_eq!(
+            integer_lines::<i64, _, _>(space1)("7 6 -4\n1  2"),
+            Ok(("", vec![vec![7, 6, -4], vec![1, 2]]))
+        );
+    }
+
+    #[test]
+    fn test_scan_integers() {
+        assert_eq!(
+            scan_integers::<i32>("p=0,4 v=3,-3\nnext"),
+            Ok(("\nnext", vec![0, 4, 3, -3]))
+        );
+        assert_eq!(
+            scan_integers::<u32>("p=0,4 v=3,-3"),
+            Ok(("", vec![0, 4, 3, 3]))
+        );
+        assert_eq!(scan_integers::<u32>("none here"), Ok(("", vec![])));
+    }
+}
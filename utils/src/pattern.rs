@@ -0,0 +1,139 @@
+//! Searching a grid for a small 2D pattern, e.g. the X-MAS shape
+//!
+//! A pattern is a grid of cells that must match, with wildcards for cells that can be anything.
+//! Matches are reported by their anchor, the point in the searched grid under the top left cell of
+//! the pattern.
+use std::str::FromStr;
+
+use crate::{Grid, GridError, Point, StaticGrid};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern<T> {
+    /// The pattern, followed by any distinct rotations and reflections being searched for too
+    variants: Vec<StaticGrid<Option<T>>>,
+}
+
+impl<T> Pattern<T>
+where
+    T: PartialEq,
+{
+    /// A pattern where `None` cells are wildcards
+    pub fn new(cells: StaticGrid<Option<T>>) -> Self {
+        Pattern {
+            variants: vec![cells],
+        }
+    }
+
+    /// Also matches every rotation and reflection of the pattern
+    ///
+    /// The anchor is still the top left of whichever variant matched, so a non-square pattern can
+    /// match with its rotated shape.
+    pub fn with_symmetries(self) -> Self
+    where
+        T: Clone,
+    {
+        let mut variants: Vec<StaticGrid<Option<T>>> = Vec::with_capacity(8);
+        for variant in self.variants[0].symmetries() {
+            if !variants.contains(&variant) {
+                variants.push(variant);
+            }
+        }
+        Pattern { variants }
+    }
+
+    /// True if the pattern (or one of its variants) has its top left cell at `anchor` in the grid
+    pub fn matches_at<G: Grid<Item = T>>(&self, grid: &G, anchor: Point) -> bool {
+        self.variants
+            .iter()
+            .any(|variant| variant_matches_at(variant, grid, anchor))
+    }
+
+    /// The anchor of every match in the grid, row by row. Each anchor is only reported once, even
+    /// if several variants match there.
+    pub fn find<'a, G>(&'a self, grid: &'a G) -> impl Iterator<Item = Point> + 'a
+    where
+        G: Grid<Item = T>,
+    {
+        grid.coords()
+            .filter(move |&anchor| self.matches_at(grid, anchor))
+    }
+}
+
+fn variant_matches_at<T, G>(variant: &StaticGrid<Option<T>>, grid: &G, anchor: Point) -> bool
+where
+    T: PartialEq,
+    G: Grid<Item = T>,
+{
+    let num_cols = variant.num_cols();
+    variant
+        .cells()
+        .iter()
+        .enumerate()
+        .all(|(ndx, wanted)| match wanted {
+            None => true,
+            Some(wanted) => {
+                let offset = Point::new((ndx % num_cols) as isize, (ndx / num_cols) as isize);
+                grid.get(anchor + offset) == Some(wanted)
+            }
+        })
+}
+
+/// Reads a pattern with rows separated by '/' or new lines and '.' as the wildcard, e.g.
+/// `M.S/.A./M.S`
+impl FromStr for Pattern<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s
+            .split(['/', '\n'])
+            .map(|row| {
+                row.trim_end_matches('\r')
+                    .chars()
+                    .map(|c| (c != '.').then_some(c))
+                    .collect()
+            })
+            .collect();
+        Ok(Pattern::new(StaticGrid::from_rows(rows)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pattern;
+    use crate::{GridError, Point, StaticGrid};
+
+    #[test]
+    fn test_pattern() {
+        let grid: StaticGrid<char> = "M.S.M.M\n.A...A.\nM.S.S.S".parse().unwrap();
+        let x_mas: Pattern<char> = "M.S/.A./M.S".parse().unwrap();
+        assert_eq!(
+            x_mas.find(&grid).collect::<Vec<_>>(),
+            vec![Point::new(0, 0)]
+        );
+        assert!(!x_mas.matches_at(&grid, Point::new(4, 0)));
+
+        let x_mas = x_mas.with_symmetries();
+        assert!(x_mas.matches_at(&grid, Point::new(4, 0)));
+        assert_eq!(
+            x_mas.find(&grid).collect::<Vec<_>>(),
+            vec![Point::new(0, 0), Point::new(4, 0)]
+        );
+
+        // A non-square pattern matches with its rotated shape too
+        let pair: Pattern<char> = "M/./M".parse().unwrap();
+        assert_eq!(pair.find(&grid).collect::<Vec<_>>(), vec![Point::new(0, 0)]);
+        assert_eq!(
+            pair.with_symmetries().find(&grid).collect::<Vec<_>>(),
+            vec![Point::new(0, 0), Point::new(4, 0)]
+        );
+
+        assert_eq!(
+            "ab/c".parse::<Pattern<char>>(),
+            Err(GridError::RaggedRow {
+                row: 1,
+                len: 1,
+                expected: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,374 @@
+//! Searching over any kind of state, e.g. day6's `(Point, CardinalDirection)` guard
+//!
+//! Describe the puzzle with a `SearchProblem`, then pick a search: `bfs` for the fewest moves,
+//! `dfs` for any path at all, `dijkstra` or `astar` for the cheapest path, and `dijkstra_all` when
+//! every cheapest path matters.
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+pub trait SearchProblem {
+    type State: Clone + Eq + Hash;
+    /// `Default` must be zero
+    type Cost: Copy + Ord + Add<Output = Self::Cost> + Default;
+
+    fn start_states(&self) -> impl IntoIterator<Item = Self::State>;
+
+    /// The states one move away, with the cost of the move
+    fn successors(
+        &self,
+        state: &Self::State,
+    ) -> impl IntoIterator<Item = (Self::State, Self::Cost)>;
+
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    /// A lower bound on the cost from the state to a goal, only used by `astar`. If it ever
+    /// overestimates, A* can miss the cheapest path.
+    fn heuristic(&self, _state: &Self::State) -> Self::Cost {
+        Self::Cost::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution<S, C> {
+    pub cost: C,
+    /// From a start state to the goal inclusive
+    pub path: Vec<S>,
+}
+
+/// Every cheapest way to a goal, from `dijkstra_all`
+#[derive(Debug, Clone)]
+pub struct AllSolutions<S, C> {
+    pub cost: C,
+    /// Every goal state reached at the cheapest cost
+    pub goals: Vec<S>,
+    /// For each reached state, the states it can be reached from at its cheapest cost. With zero
+    /// cost moves these can form cycles.
+    pub predecessors: HashMap<S, Vec<S>>,
+}
+
+impl<S, C> AllSolutions<S, C>
+where
+    S: Clone + Eq + Hash,
+{
+    /// Every state on at least one cheapest path
+    pub fn states(&self) -> HashSet<S> {
+        let mut states: HashSet<S> = self.goals.iter().cloned().collect();
+        let mut to_visit = self.goals.clone();
+        while let Some(state) = to_visit.pop() {
+            for previous in self.predecessors.get(&state).into_iter().flatten() {
+                if states.insert(previous.clone()) {
+                    to_visit.push(previous.clone());
+                }
+            }
+        }
+        states
+    }
+}
+
+struct Node<S, C> {
+    state: S,
+    cost: C,
+    /// The first parent is the one paths are rebuilt through
+    parents: Vec<usize>,
+}
+
+/// Every state seen so far, stored once and referred to by index
+struct Explored<S, C> {
+    nodes: Vec<Node<S, C>>,
+    index: HashMap<S, usize>,
+}
+
+impl<S, C> Explored<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord,
+{
+    fn new() -> Self {
+        Explored {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// Adds a state reached for `cost`, returning its index if it hasn't been seen before
+    fn insert_new(&mut self, state: S, cost: C, parent: Option<usize>) -> Option<usize> {
+        if self.index.contains_key(&state) {
+            return None;
+        }
+        self.index.insert(state.clone(), self.nodes.len());
+        self.nodes.push(Node {
+            state,
+            cost,
+            parents: parent.into_iter().collect(),
+        });
+        Some(self.nodes.len() - 1)
+    }
+
+    /// Records reaching a state for `cost`, returning its index if that is the cheapest so far.
+    /// With `keep_all`, parents reaching it for the same cost are remembered too.
+    fn relax(&mut self, state: S, cost: C, parent: Option<usize>, keep_all: bool) -> Option<usize> {
+        let Some(&ndx) = self.index.get(&state) else {
+            return self.insert_new(state, cost, parent);
+        };
+        let node = &mut self.nodes[ndx];
+        if cost < node.cost {
+            node.cost = cost;
+            node.parents = parent.into_iter().collect();
+            Some(ndx)
+        } else {
+            if let Some(parent) = parent {
+                if keep_all && cost == node.cost && !node.parents.contains(&parent) {
+                    node.parents.push(parent);
+                }
+            }
+            None
+        }
+    }
+
+    fn solution(&self, mut ndx: usize) -> Solution<S, C> {
+        let cost = self.nodes[ndx].cost;
+        let mut path = vec![self.nodes[ndx].state.clone()];
+        while let Some(&parent) = self.nodes[ndx].parents.first() {
+            path.push(self.nodes[parent].state.clone());
+            ndx = parent;
+        }
+        path.reverse();
+        Solution { cost, path }
+    }
+}
+
+/// The path with the fewest moves, ignoring costs while searching
+///
+/// The cost is the total cost along that path, which isn't necessarily the cheapest.
+pub fn bfs<P: SearchProblem>(problem: &P) -> Option<Solution<P::State, P::Cost>> {
+    let mut explored = Explored::new();
+    let mut queue: VecDeque<usize> = problem
+        .start_states()
+        .into_iter()
+        .filter_map(|state| explored.insert_new(state, P::Cost::default(), None))
+        .collect();
+    while let Some(ndx) = queue.pop_front() {
+        let state = explored.nodes[ndx].state.clone();
+        if problem.is_goal(&state) {
+            return Some(explored.solution(ndx));
+        }
+        for (next, step) in problem.successors(&state) {
+            let cost = explored.nodes[ndx].cost + step;
+            queue.extend(explored.insert_new(next, cost, Some(ndx)));
+        }
+    }
+    None
+}
+
+/// Any path to a goal, going as deep as possible before backtracking
+pub fn dfs<P: SearchProblem>(problem: &P) -> Option<Solution<P::State, P::Cost>> {
+    let mut explored = Explored::new();
+    let mut stack: Vec<usize> = problem
+        .start_states()
+        .into_iter()
+        .filter_map(|state| explored.insert_new(state, P::Cost::default(), None))
+        .collect();
+    stack.reverse();
+    while let Some(ndx) = stack.pop() {
+        let state = explored.nodes[ndx].state.clone();
+        if problem.is_goal(&state) {
+            return Some(explored.solution(ndx));
+        }
+        let first_child = stack.len();
+        for (next, step) in problem.successors(&state) {
+            let cost = explored.nodes[ndx].cost + step;
+            stack.extend(explored.insert_new(next, cost, Some(ndx)));
+        }
+        // Visit the successors in the order they were given
+        stack[first_child..].reverse();
+    }
+    None
+}
+
+/// Cheapest first search shared by Dijkstra and A*, returning every goal found at the cheapest
+/// cost (only the first unless `keep_all`)
+fn best_first<P: SearchProblem>(
+    problem: &P,
+    use_heuristic: bool,
+    keep_all: bool,
+) -> (Explored<P::State, P::Cost>, Vec<usize>) {
+    let estimate = |state: &P::State, cost: P::Cost| {
+        if use_heuristic {
+            cost + problem.heuristic(state)
+        } else {
+            cost
+        }
+    };
+    let zero = P::Cost::default();
+    let mut explored = Explored::new();
+    let mut heap = BinaryHeap::new();
+    for state in problem.start_states() {
+        let priority = estimate(&state, zero);
+        if let Some(ndx) = explored.relax(state, zero, None, keep_all) {
+            heap.push(Reverse((priority, zero, ndx)));
+        }
+    }
+
+    let mut goals = Vec::new();
+    let mut best = None;
+    while let Some(Reverse((priority, cost, ndx))) = heap.pop() {
+        // A cheaper way here was found after this entry was queued
+        if cost > explored.nodes[ndx].cost {
+            continue;
+        }
+        if best.is_some_and(|best| priority > best) {
+            break;
+        }
+        let state = explored.nodes[ndx].state.clone();
+        if problem.is_goal(&state) {
+            best = Some(cost);
+            goals.push(ndx);
+            if keep_all {
+                continue;
+            }
+            break;
+        }
+        for (next, step) in problem.successors(&state) {
+            let next_cost = cost + step;
+            let priority = estimate(&next, next_cost);
+            if let Some(next_ndx) = explored.relax(next, next_cost, Some(ndx), keep_all) {
+                heap.push(Reverse((priority, next_cost, next_ndx)));
+            }
+        }
+    }
+    (explored, goals)
+}
+
+/// The cheapest path to a goal
+pub fn dijkstra<P: SearchProblem>(problem: &P) -> Option<Solution<P::State, P::Cost>> {
+    let (explored, goals) = best_first(problem, false, false);
+    goals.first().map(|&goal| explored.solution(goal))
+}
+
+/// The cheapest path to a goal, using the problem's heuristic to look at fewer states
+pub fn astar<P: SearchProblem>(problem: &P) -> Option<Solution<P::State, P::Cost>> {
+    let (explored, goals) = best_first(problem, true, false);
+    goals.first().map(|&goal| explored.solution(goal))
+}
+
+/// Every cheapest path to any goal, e.g. to find all the cells on a best path
+pub fn dijkstra_all<P: SearchProblem>(problem: &P) -> Option<AllSolutions<P::State, P::Cost>> {
+    let (explored, goals) = best_first(problem, false, true);
+    let cost = explored.nodes[*goals.first()?].cost;
+    let predecessors = explored
+        .nodes
+        .iter()
+        .filter(|node| !node.parents.is_empty())
+        .map(|node| {
+            let parents = node
+                .parents
+                .iter()
+                .map(|&parent| explored.nodes[parent].state.clone())
+                .collect();
+            (node.state.clone(), parents)
+        })
+        .collect();
+    Some(AllSolutions {
+        cost,
+        goals: goals
+            .iter()
+            .map(|&goal| explored.nodes[goal].state.clone())
+            .collect(),
+        predecessors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{astar, bfs, dfs, dijkstra, dijkstra_all, SearchProblem};
+    use crate::{CardinalDirection, Grid, Point, RotateAmount, StaticGrid};
+
+    /// Walking a maze, where turning on the spot can cost more than moving
+    struct Maze {
+        grid: StaticGrid<char>,
+        turn_cost: usize,
+        end: Point,
+    }
+
+    impl SearchProblem for Maze {
+        type State = (Point, CardinalDirection);
+        type Cost = usize;
+
+        fn start_states(&self) -> impl IntoIterator<Item = Self::State> {
+            let start = self.grid.position(|&c| c == 'S').unwrap();
+            [(start, CardinalDirection::East)]
+        }
+
+        fn successors(
+            &self,
+            &(point, direction): &Self::State,
+        ) -> impl IntoIterator<Item = (Self::State, Self::Cost)> {
+            let mut next = vec![
+                (
+                    (point, direction.rotate_by_angle(&RotateAmount::_90)),
+                    self.turn_cost,
+                ),
+                (
+                    (point, direction.rotate_by_angle(&RotateAmount::_270)),
+                    self.turn_cost,
+                ),
+            ];
+            let ahead = point.step(direction, 1);
+            if self.grid.get(ahead).is_some_and(|&c| c != '#') {
+                next.push(((ahead, direction), 1));
+            }
+            next
+        }
+
+        fn is_goal(&self, &(point, _): &Self::State) -> bool {
+            point == self.end
+        }
+
+        fn heuristic(&self, &(point, _): &Self::State) -> Self::Cost {
+            point.manhattan(self.end)
+        }
+    }
+
+    #[test]
+    fn test_search() {
+        let grid: StaticGrid<char> = "######\n#..E.#\n#.#.##\n#S...#\n######".parse().unwrap();
+        let end = grid.position(|&c| c == 'E').unwrap();
+        let mut maze = Maze {
+            grid,
+            turn_cost: 1000,
+            end,
+        };
+
+        // East then North beats North then East, which needs an extra turn
+        let best = dijkstra(&maze).unwrap();
+        assert_eq!(best.cost, 1004);
+        assert_eq!(best.path.len(), 6);
+        assert_eq!(best.path[0], (Point::new(1, 3), CardinalDirection::East));
+        assert_eq!(best.path[5], (end, CardinalDirection::North));
+        assert_eq!(astar(&maze), Some(best.clone()));
+        assert_eq!(bfs(&maze), Some(best));
+
+        let any = dfs(&maze).unwrap();
+        assert!(any.cost >= 1004);
+        assert_eq!(any.path.last().unwrap().0, end);
+
+        // Without turning costs both ways around the wall are just as good
+        maze.turn_cost = 0;
+        let all = dijkstra_all(&maze).unwrap();
+        assert_eq!(all.cost, 4);
+        let cells: HashSet<Point> = all.states().into_iter().map(|(p, _)| p).collect();
+        assert_eq!(cells.len(), 8);
+        assert!(!cells.contains(&Point::new(4, 1)));
+        assert!(!cells.contains(&Point::new(4, 3)));
+
+        maze.end = Point::new(0, 0);
+        assert_eq!(dijkstra(&maze), None);
+        assert!(dijkstra_all(&maze).is_none());
+    }
+}
@@ -0,0 +1,199 @@
+//! Finding a list of words in a grid of letters, like day4's XMAS
+//!
+//! The words are stored in a trie, so each start cell and direction is only walked as far as some
+//! word still matches, however many words there are.
+use crate::{CardinalDirection, Grid, Point, StaticGrid};
+
+/// Whether found words may share cells
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Overlap {
+    /// Report every occurrence
+    #[default]
+    Allowed,
+    /// Each cell belongs to at most one word. Occurrences are taken in search order (row by row,
+    /// then in the order of the directions, longest word first), skipping any that would reuse a
+    /// cell.
+    Disjoint,
+}
+
+/// One occurrence of a word
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct WordMatch {
+    /// Index of the word in the list given to `WordSearch::new`
+    pub word: usize,
+    pub start: Point,
+    pub direction: CardinalDirection,
+    pub len: usize,
+}
+
+impl WordMatch {
+    /// The cells of the word, from its first letter to its last
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.len as isize).map(|n| self.start.step(self.direction, n))
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct TrieNode {
+    children: Vec<(char, usize)>,
+    word: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WordSearch {
+    words: Vec<String>,
+    nodes: Vec<TrieNode>,
+    directions: Vec<CardinalDirection>,
+    overlap: Overlap,
+}
+
+impl WordSearch {
+    /// Searches for the words in all 8 directions, allowing overlaps
+    ///
+    /// Empty words are never found, and a repeated word is only reported under its first index.
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut search = WordSearch {
+            words: words.into_iter().map(Into::into).collect(),
+            nodes: vec![TrieNode::default()],
+            directions: CardinalDirection::ALL.to_vec(),
+            overlap: Overlap::default(),
+        };
+        for ndx in 0..search.words.len() {
+            search.insert(ndx);
+        }
+        search
+    }
+
+    fn insert(&mut self, word_ndx: usize) {
+        let mut node = 0;
+        for c in self.words[word_ndx].chars() {
+            node = match self.child(node, c) {
+                Some(child) => child,
+                None => {
+                    self.nodes.push(TrieNode::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.push((c, child));
+                    child
+                }
+            };
+        }
+        if node != 0 && self.nodes[node].word.is_none() {
+            self.nodes[node].word = Some(word_ndx);
+        }
+    }
+
+    fn child(&self, node: usize, c: char) -> Option<usize> {
+        self.nodes[node]
+            .children
+            .iter()
+            .find(|(child_c, _)| *child_c == c)
+            .map(|&(_, child)| child)
+    }
+
+    /// Only reads words in these directions, e.g. `CardinalDirection::ORTHOGONAL`
+    pub fn with_directions(mut self, directions: &[CardinalDirection]) -> Self {
+        self.directions = directions.to_vec();
+        self
+    }
+
+    pub fn with_overlap(mut self, overlap: Overlap) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    pub fn word(&self, ndx: usize) -> &str {
+        &self.words[ndx]
+    }
+
+    /// Every occurrence of the words, row by row by start point. A one letter word reads the same
+    /// in every direction, so it is only reported once, with the first direction.
+    pub fn find(&self, grid: &StaticGrid<char>) -> Vec<WordMatch> {
+        let mut used = vec![false; grid.cells().len()];
+        let cell_ndx = |p: Point| p.y as usize * grid.num_cols() + p.x as usize;
+
+        let mut found = Vec::new();
+        let mut at_start = Vec::new();
+        for start in grid.coords() {
+            for (direction_ndx, &direction) in self.directions.iter().enumerate() {
+                // Walk the trie as far as the letters in this direction allow
+                let mut node = 0;
+                let mut len = 0;
+                while let Some(next) = grid
+                    .get(start.step(direction, len as isize))
+                    .and_then(|&c| self.child(node, c))
+                {
+                    node = next;
+                    len += 1;
+                    if let Some(word) = self.nodes[node]
+                        .word
+                        .filter(|_| len > 1 || direction_ndx == 0)
+                    {
+                        at_start.push(WordMatch {
+                            word,
+                            start,
+                            direction,
+                            len,
+                        });
+                    }
+                }
+                for word_match in at_start.drain(..).rev() {
+                    if self.overlap == Overlap::Disjoint {
+                        if word_match.points().any(|p| used[cell_ndx(p)]) {
+                            continue;
+                        }
+                        word_match.points().for_each(|p| used[cell_ndx(p)] = true);
+                    }
+                    found.push(word_match);
+                }
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Overlap, WordMatch, WordSearch};
+    use crate::{CardinalDirection, Point, StaticGrid};
+
+    #[test]
+    fn test_word_search() {
+        let grid: StaticGrid<char> = "CATS\nAXOX\nRODE\nTXGX".parse().unwrap();
+        let search = WordSearch::new(["CAT", "CATS", "CART", "DOT", "ODE", "TOO", "", "CAT"]);
+        let found = search.find(&grid);
+        let words: Vec<&str> = found.iter().map(|m| search.word(m.word)).collect();
+        assert_eq!(words, vec!["CATS", "CAT", "CART", "ODE", "DOT", "TOO"]);
+        assert_eq!(
+            found[4],
+            WordMatch {
+                word: 3,
+                start: Point::new(2, 2),
+                direction: CardinalDirection::North,
+                len: 3
+            }
+        );
+        assert_eq!(
+            found[5].points().collect::<Vec<_>>(),
+            vec![Point::new(0, 3), Point::new(1, 2), Point::new(2, 1)]
+        );
+
+        let search = search.with_directions(&CardinalDirection::ORTHOGONAL);
+        assert_eq!(search.find(&grid).len(), 5);
+
+        // CAT and CART share the C with CATS, DOT shares the D with ODE
+        let search = search.with_overlap(Overlap::Disjoint);
+        let words: Vec<&str> = search
+            .find(&grid)
+            .iter()
+            .map(|m| search.word(m.word))
+            .collect();
+        assert_eq!(words, vec!["CATS", "ODE"]);
+
+        let grid: StaticGrid<char> = "XAX".parse().unwrap();
+        assert_eq!(WordSearch::new(["A"]).find(&grid).len(), 1);
+    }
+}
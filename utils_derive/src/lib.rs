@@ -0,0 +1,277 @@
+//! Derive macro for `utils::AocParse`.
+//!
+//! Generates a nom parser for a struct or enum from its fields, so simple puzzle inputs can be
+//! loaded with `utils::load_puzzle_data(day, Type::parse)` without hand-written combinators.
+//! Use it through the `derive` feature of `utils`, which re-exports it as `utils::AocParse`.
+//!
+//! Attributes (`#[aoc(...)]`):
+//!  * On the struct / enum variant:
+//!    * `before = "lit"` - literal consumed before the first field
+//!    * `after = "lit"` - literal consumed after the last field
+//!    * `sep = ...` - separator consumed between fields
+//!  * On a field:
+//!    * `before = "lit"` / `after = "lit"` - literal consumed around this field
+//!    * `repeat` - the field is a `Vec<T>`, parse one or more `T`
+//!    * `sep = ...` - with `repeat`, separator consumed between elements
+//!
+//! A separator is either a string literal (matched exactly) or one of the keywords
+//! `space` (one or more spaces/tabs), `whitespace` (one or more of any whitespace) or
+//! `line` (a line ending).
+//!
+//! ```ignore
+//! #[derive(AocParse)]
+//! #[aoc(before = "mul(", sep = ",", after = ")")]
+//! struct Mul(u32, u32);
+//! ```
+//!
+//! Enum variants are tried in declaration order, the first one to match wins.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Fields, GenericArgument, Ident, LitStr,
+    PathArguments, Type,
+};
+
+#[proc_macro_derive(AocParse, attributes(aoc))]
+pub fn derive_aoc_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+enum Separator {
+    Tag(LitStr),
+    Space,
+    Whitespace,
+    Line,
+}
+
+#[derive(Default)]
+struct AocAttrs {
+    before: Option<LitStr>,
+    after: Option<LitStr>,
+    sep: Option<Separator>,
+    repeat: bool,
+}
+
+impl AocAttrs {
+    fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut aoc_attrs = AocAttrs::default();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("aoc")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("before") {
+                    aoc_attrs.before = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("after") {
+                    aoc_attrs.after = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("sep") {
+                    let value = meta.value()?;
+                    aoc_attrs.sep =
+                        Some(if value.peek(LitStr) {
+                            Separator::Tag(value.parse()?)
+                        } else {
+                            let ident: Ident = value.parse()?;
+                            match ident.to_string().as_str() {
+                                "space" => Separator::Space,
+                                "whitespace" => Separator::Whitespace,
+                                "line" => Separator::Line,
+                                _ => return Err(syn::Error::new(
+                                    ident.span(),
+                                    "expected a string literal, `space`, `whitespace` or `line`",
+                                )),
+                            }
+                        });
+                } else if meta.path.is_ident("repeat") {
+                    aoc_attrs.repeat = true;
+                } else {
+                    return Err(meta.error("unknown aoc attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(aoc_attrs)
+    }
+}
+
+/// An expression that can be called as `fn(&str) -> IResult<&str, &str>`
+fn separator_parser(sep: &Separator) -> TokenStream2 {
+    match sep {
+        Separator::Tag(lit) => quote! {
+            ::utils::nom::bytes::complete::tag::<&str, &str, ::utils::nom::error::Error<&str>>(#lit)
+        },
+        Separator::Space => quote! {
+            ::utils::nom::character::complete::space1::<&str, ::utils::nom::error::Error<&str>>
+        },
+        Separator::Whitespace => quote! {
+            ::utils::nom::character::complete::multispace1::<&str, ::utils::nom::error::Error<&str>>
+        },
+        Separator::Line => quote! {
+            ::utils::nom::character::complete::line_ending::<&str, ::utils::nom::error::Error<&str>>
+        },
+    }
+}
+
+fn literal(lit: &Option<LitStr>) -> TokenStream2 {
+    match lit {
+        Some(lit) => {
+            let parser = separator_parser(&Separator::Tag(lit.clone()));
+            quote! { let (input, _) = #parser(input)?; }
+        }
+        None => quote! {},
+    }
+}
+
+fn vec_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Vec" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(GenericArgument::Type(inner)) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Parses the fields (and the surrounding literals) of a struct or variant, then returns it
+fn parse_body(
+    container_attrs: &[Attribute],
+    fields: &Fields,
+    constructor: TokenStream2,
+) -> syn::Result<TokenStream2> {
+    let container = AocAttrs::from_attrs(container_attrs)?;
+    if container.repeat {
+        return Err(syn::Error::new_spanned(
+            &constructor,
+            "`repeat` is only valid on fields",
+        ));
+    }
+    if fields.is_empty() && container.before.is_none() && container.after.is_none() {
+        return Err(syn::Error::new_spanned(
+            &constructor,
+            "a type without fields needs `before` or `after` to be parsed",
+        ));
+    }
+
+    let mut steps = Vec::new();
+    let mut bindings = Vec::new();
+    for (field_ndx, field) in fields.iter().enumerate() {
+        let attrs = AocAttrs::from_attrs(&field.attrs)?;
+        if field_ndx > 0 {
+            if let Some(sep) = &container.sep {
+                let sep = separator_parser(sep);
+                steps.push(quote! { let (input, _) = #sep(input)?; });
+            }
+        }
+        steps.push(literal(&attrs.before));
+
+        // Never bind a field under its own name, it could shadow `input`
+        let binding = format_ident!("__field{}", field_ndx);
+        let ty = &field.ty;
+        let value = if attrs.repeat {
+            let inner = vec_inner_type(ty)
+                .ok_or_else(|| syn::Error::new_spanned(ty, "`repeat` requires a `Vec<T>` field"))?;
+            match &attrs.sep {
+                Some(sep) => {
+                    let sep = separator_parser(sep);
+                    quote! {
+                        ::utils::nom::multi::separated_list1(
+                            #sep,
+                            <#inner as ::utils::AocParse>::parse,
+                        )(input)?
+                    }
+                }
+                None => quote! {
+                    ::utils::nom::multi::many1(<#inner as ::utils::AocParse>::parse)(input)?
+                },
+            }
+        } else {
+            if attrs.sep.is_some() {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "`sep` on a field is only valid together with `repeat`",
+                ));
+            }
+            quote! { <#ty as ::utils::AocParse>::parse(input)? }
+        };
+        steps.push(quote! { let (input, #binding) = #value; });
+        steps.push(literal(&attrs.after));
+        bindings.push(match &field.ident {
+            Some(ident) => quote! { #ident: #binding },
+            None => quote! { #binding },
+        });
+    }
+
+    let before = literal(&container.before);
+    let after = literal(&container.after);
+    let construct = match fields {
+        Fields::Named(_) => quote! { #constructor { #(#bindings),* } },
+        Fields::Unnamed(_) => quote! { #constructor ( #(#bindings),* ) },
+        Fields::Unit => quote! { #constructor },
+    };
+    Ok(quote! {
+        #before
+        #(#steps)*
+        #after
+        Ok((input, #construct))
+    })
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let body = match &input.data {
+        Data::Struct(data) => parse_body(&input.attrs, &data.fields, quote! { #name })?,
+        Data::Enum(data) => {
+            if data.variants.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "cannot derive AocParse for an enum without variants",
+                ));
+            }
+            let mut attempts = Vec::new();
+            for variant in data.variants.iter() {
+                let variant_name = &variant.ident;
+                let variant_body = parse_body(
+                    &variant.attrs,
+                    &variant.fields,
+                    quote! { #name::#variant_name },
+                )?;
+                attempts.push(quote! {
+                    let attempt = (|| -> ::utils::nom::IResult<_, Self> { #variant_body })();
+                    match attempt {
+                        Err(::utils::nom::Err::Error(_)) => {}
+                        result => return result,
+                    }
+                });
+            }
+            quote! {
+                #(#attempts)*
+                Err(::utils::nom::Err::Error(::utils::nom::error::Error::new(
+                    input,
+                    ::utils::nom::error::ErrorKind::Alt,
+                )))
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "cannot derive AocParse for a union",
+            ))
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::utils::AocParse for #name #ty_generics #where_clause {
+            fn parse(input: &str) -> ::utils::nom::IResult<&str, Self> {
+                #body
+            }
+        }
+    })
+}
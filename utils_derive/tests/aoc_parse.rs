@@ -0,0 +1,70 @@
+use utils::AocParse;
+
+#[test]
+fn test_derive_aoc_parse() {
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(before = "mul(", sep = ",", after = ")")]
+    struct Mul(u32, u32);
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(sep = ": ")]
+    struct Equation {
+        result: u64,
+        #[aoc(repeat, sep = space)]
+        operands: Vec<u64>,
+    }
+
+    // A field may share its name with the parser's own state
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(sep = " -> ")]
+    struct Wire {
+        input: u32,
+        output: u32,
+    }
+
+    #[derive(AocParse, Debug, PartialEq)]
+    enum Instruction {
+        #[aoc(before = "do()")]
+        Do,
+        #[aoc(before = "don't()")]
+        Dont,
+        Mul(Mul),
+    }
+
+    assert_eq!(Mul::parse("mul(2,4)rest"), Ok(("rest", Mul(2, 4))));
+    assert!(Mul::parse("mul(2 4)").is_err());
+    assert_eq!(
+        utils::parse_lines::<Equation>("190: 10 19\n3267: 81 40 27"),
+        Ok((
+            "",
+            vec![
+                Equation {
+                    result: 190,
+                    operands: vec![10, 19]
+                },
+                Equation {
+                    result: 3267,
+                    operands: vec![81, 40, 27]
+                }
+            ]
+        ))
+    );
+    assert_eq!(
+        Wire::parse("3 -> 7"),
+        Ok((
+            "",
+            Wire {
+                input: 3,
+                output: 7
+            }
+        ))
+    );
+    assert_eq!(Instruction::parse("don't()"), Ok(("", Instruction::Dont)));
+    assert_eq!(
+        Instruction::parse("mul(3,5)"),
+        Ok(("", Instruction::Mul(Mul(3, 5))))
+    );
+    assert!(Instruction::parse("undo()").is_err());
+    // Overflow is a parse error, not a wrap around
+    assert!(u8::parse("256").is_err());
+}